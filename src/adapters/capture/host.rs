@@ -0,0 +1,99 @@
+use crate::adapters::hostfs::HostFs;
+use crate::adapters::proc::linux::cmdline::get_cmdline;
+use crate::adapters::proc::ProcFs;
+use crate::adapters::source;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const HOST_FILES: &[&str] = &[
+    "/etc/passwd",
+    "/etc/crontab",
+    "/etc/anacrontab",
+    "/etc/supervisord.conf",
+];
+// Crontabs, supervisord configs, init scripts and service directories, as
+// far down as the cron, supervisord and init system adapters look.
+const HOST_TREES: &[&str] = &[
+    "/etc/cron.d",
+    "/var/spool/cron",
+    "/var/spool/anacron",
+    "/etc/supervisor",
+    "/etc/init.d",
+    "/run/openrc",
+    "/etc/service",
+    "/etc/sv",
+    "/var/service",
+    "/run/service",
+];
+const TREE_DEPTH: usize = 3;
+// Pid files sit directly in `/run` or in a directory of the daemon's own.
+// `/var/run` only links there.
+const PID_DIR: &str = "/run";
+const PM2_HOMES: &[&str] = &["/root/.pm2"];
+
+// The host files the adapters read, as absolute host paths: the fixed
+// ones, plus each running supervisord's config tree and each PM2 home's
+// process list and pid files.
+pub(super) fn host_files(procfs: &ProcFs, host: &HostFs, pids: &[u32]) -> BTreeSet<PathBuf> {
+    let mut files: BTreeSet<PathBuf> = HOST_FILES.iter().map(PathBuf::from).collect();
+    for abs in HOST_TREES {
+        tree(host, Path::new(abs), TREE_DEPTH, &mut files);
+    }
+    let mut run = BTreeSet::new();
+    tree(host, Path::new(PID_DIR), 1, &mut run);
+    files.extend(
+        run.into_iter()
+            .filter(|p| p.extension() == Some("pid".as_ref())),
+    );
+
+    let mut homes: BTreeSet<String> = PM2_HOMES.iter().map(|h| h.to_string()).collect();
+    if let Ok(entries) = fs::read_dir(host.path("/home")) {
+        for entry in entries.flatten() {
+            homes.insert(format!(
+                "/home/{}/.pm2",
+                entry.file_name().to_string_lossy()
+            ));
+        }
+    }
+    for &pid in pids {
+        let cmd = get_cmdline(procfs, pid);
+        if source::is_supervisord("", &cmd) {
+            if let Some(config) = source::supervisor_config_path(host, &cmd) {
+                let config = PathBuf::from(config);
+                if let Some(dir) = config.parent() {
+                    tree(host, dir, TREE_DEPTH, &mut files);
+                }
+                files.insert(config);
+            }
+        }
+        if source::is_pm2_daemon(&cmd) {
+            homes.extend(source::pm2_home(&cmd, &[]));
+        }
+    }
+    for home in homes {
+        let home = Path::new(&home);
+        files.insert(home.join("dump.pm2"));
+        tree(host, &home.join("pids"), 0, &mut files);
+    }
+    files
+}
+
+// Regular files under `dir`, following symlinks, up to `depth` directories
+// further down. Fifos and sockets, such as a service directory's
+// `supervise/control`, are skipped.
+fn tree(host: &HostFs, dir: &Path, depth: usize, files: &mut BTreeSet<PathBuf>) {
+    let Ok(entries) = fs::read_dir(host.path(dir)) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = dir.join(entry.file_name());
+        match fs::metadata(entry.path()) {
+            Ok(meta) if meta.is_file() => {
+                files.insert(path);
+            }
+            Ok(meta) if meta.is_dir() && depth > 0 => tree(host, &path, depth - 1, files),
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,247 @@
+mod host;
+
+use crate::adapters::hostfs::HostFs;
+use crate::adapters::proc::linux::cgroup::{cgroup_dir, unified_path};
+use crate::adapters::proc::linux::netns::foreign_namespaces;
+use crate::adapters::proc::ProcFs;
+use std::collections::BTreeSet;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use tar::{Builder, EntryType, Header};
+
+const SYSTEM_FILES: &[&str] = &[
+    "stat", "locks", "net/tcp", "net/tcp6", "net/udp", "net/udp6", "net/raw", "net/raw6",
+    "net/unix",
+];
+const PID_FILES: &[&str] = &[
+    "status",
+    "comm",
+    "cmdline",
+    "cgroup",
+    "limits",
+    "maps",
+    "smaps_rollup",
+    "oom_score",
+    "oom_score_adj",
+];
+const PID_LINKS: &[&str] = &["exe", "cwd", "root", "ns/net"];
+const HOST_DIRS: &[&str] = &["/run/systemd/inhibit"];
+const CGROUP_FILES: &[&str] = &[
+    "cgroup.controllers",
+    "pids.max",
+    "memory.current",
+    "memory.max",
+    "cpu.max",
+    "cpu.stat",
+    "cpu.pressure",
+    "memory.pressure",
+    "io.pressure",
+];
+
+/// Records a procfs tree, plus the host files the parsers consult, into a
+/// tar archive under `prefix`, in the layout `FixtureSystem` reads once
+/// unpacked. Process environments often hold
+/// secrets, so `environ` is only recorded when `include_env` is set.
+/// Returns the number of processes recorded.
+pub fn capture<W: Write>(
+    procfs: &ProcFs,
+    host: &HostFs,
+    prefix: &Path,
+    include_env: bool,
+    out: W,
+) -> io::Result<usize> {
+    let mut tar = Builder::new(out);
+    let mut count = 0;
+
+    for rel in SYSTEM_FILES {
+        if let Ok(data) = fs::read(procfs.path(rel)) {
+            append_file(&mut tar, &prefix.join(rel), &data)?;
+        }
+    }
+
+    let pids = procfs.pids();
+    for abs in host::host_files(procfs, host, &pids) {
+        if let Ok(data) = fs::read(host.path(&abs)) {
+            append_file(&mut tar, &HostFs::new(prefix).path(abs), &data)?;
+        }
+    }
+
+    for abs in HOST_DIRS {
+        let Ok(entries) = fs::read_dir(host.path(abs)) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            if let Ok(data) = fs::read(entry.path()) {
+                let path = Path::new(abs).join(entry.file_name());
+                append_file(&mut tar, &HostFs::new(prefix).path(path), &data)?;
+            }
+        }
+    }
+
+    // Which namespace the top-level net/ tables describe.
+    if let Ok(target) = fs::read_link(procfs.path("self/ns/net")) {
+        append_link(&mut tar, &prefix.join("self/ns/net"), &target)?;
+    }
+
+    // Processes in other network namespaces see other tables; one member's
+    // copy covers the whole namespace.
+    for members in foreign_namespaces(procfs, &pids).values() {
+        let dir = prefix.join(members[0].to_string());
+        for rel in SYSTEM_FILES.iter().filter(|rel| rel.starts_with("net/")) {
+            if let Ok(data) = fs::read(procfs.pid_path(members[0], rel)) {
+                append_file(&mut tar, &dir.join(rel), &data)?;
+            }
+        }
+    }
+
+    let mut cgroups = BTreeSet::from([String::from("/")]);
+    for pid in pids {
+        // The process may have exited since the directory was listed.
+        let Ok(stat) = fs::read(procfs.pid_path(pid, "stat")) else {
+            continue;
+        };
+        let dir = prefix.join(pid.to_string());
+        append_file(&mut tar, &dir.join("stat"), &stat)?;
+
+        if let Some(path) = fs::read_to_string(procfs.pid_path(pid, "cgroup"))
+            .ok()
+            .and_then(|c| unified_path(&c).map(str::to_string))
+        {
+            // Limits are read at every level, so record each ancestor too.
+            let mut level = String::new();
+            for name in path.split('/').filter(|n| !n.is_empty()) {
+                level = format!("{}/{}", level, name);
+                cgroups.insert(level.clone());
+            }
+        }
+        let env_file = include_env.then_some("environ");
+        for rel in PID_FILES.iter().copied().chain(env_file) {
+            if let Ok(data) = fs::read(procfs.pid_path(pid, rel)) {
+                append_file(&mut tar, &dir.join(rel), &data)?;
+            }
+        }
+        for rel in PID_LINKS {
+            if let Ok(target) = fs::read_link(procfs.pid_path(pid, rel)) {
+                append_link(&mut tar, &dir.join(rel), &target)?;
+            }
+        }
+        if let Ok(entries) = fs::read_dir(procfs.pid_path(pid, "fd")) {
+            for entry in entries.flatten() {
+                if let Ok(target) = fs::read_link(entry.path()) {
+                    append_link(&mut tar, &dir.join("fd").join(entry.file_name()), &target)?;
+                }
+                let fdinfo = Path::new("fdinfo").join(entry.file_name());
+                if let Ok(data) = fs::read(procfs.pid_path(pid, &fdinfo.to_string_lossy())) {
+                    append_file(&mut tar, &dir.join(fdinfo), &data)?;
+                }
+            }
+        }
+        count += 1;
+    }
+
+    for path in cgroups {
+        let dir = cgroup_dir(host, &path);
+        for name in CGROUP_FILES {
+            if let Ok(data) = fs::read(dir.join(name)) {
+                append_file(
+                    &mut tar,
+                    &cgroup_dir(&HostFs::new(prefix), &path).join(name),
+                    &data,
+                )?;
+            }
+        }
+    }
+
+    tar.into_inner()?.flush()?;
+    Ok(count)
+}
+
+fn append_file<W: Write>(tar: &mut Builder<W>, path: &Path, data: &[u8]) -> io::Result<()> {
+    let mut header = Header::new_gnu();
+    header.set_entry_type(EntryType::Regular);
+    header.set_size(data.len() as u64);
+    header.set_mode(0o644);
+    tar.append_data(&mut header, path, data)
+}
+
+fn append_link<W: Write>(tar: &mut Builder<W>, path: &Path, target: &Path) -> io::Result<()> {
+    let mut header = Header::new_gnu();
+    header.set_entry_type(EntryType::Symlink);
+    header.set_size(0);
+    header.set_mode(0o777);
+    tar.append_link(&mut header, path, target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::testutil::TempTree;
+
+    #[test]
+    fn test_capture_round_trip() {
+        let tree = TempTree::new("capture");
+        tree.write("etc/passwd", "root:x:0:0::/root:/bin/sh\n")
+            .write("stat", "btime 1700000000\n")
+            .write("net/tcp", "header\n")
+            .write("42/stat", "42 (app) S 1 42 42 0")
+            .write("42/environ", "SECRET=1\0")
+            .symlink("socket:[777]", "42/fd/3")
+            .write("42/fdinfo/3", "pos:\t0\n")
+            .symlink("/usr/bin/app", "42/exe")
+            .symlink("net:[4026532300]", "42/ns/net")
+            .write("42/net/tcp", "header\n")
+            .write("42/cgroup", "0::/app.slice\n")
+            .write("sys/fs/cgroup/app.slice/memory.max", "max\n")
+            .write("run/systemd/inhibit/1", "PID=42\n")
+            .write("etc/cron.d/backup", "0 2 * * * root backup\n")
+            .write("run/named/named.pid", "42\n")
+            .write("run/utmp", "")
+            .write("etc/service/app/run", "#!/bin/sh\n")
+            .mkdir("etc/service/app/supervise");
+        let control = tree.path("etc/service/app/supervise/control");
+        let _listener = std::os::unix::net::UnixListener::bind(control).unwrap();
+
+        let mut buf = Vec::new();
+        let (procfs, host) = (ProcFs::new(tree.root()), tree.host());
+        let count = capture(&procfs, &host, Path::new("snap"), false, &mut buf).unwrap();
+        assert_eq!(count, 1);
+
+        let mut archive = tar::Archive::new(buf.as_slice());
+        let mut entries: Vec<(String, Option<String>)> = archive
+            .entries()
+            .unwrap()
+            .map(|e| {
+                let e = e.unwrap();
+                let path = e.path().unwrap().display().to_string();
+                let link = e.link_name().unwrap().map(|l| l.display().to_string());
+                (path, link)
+            })
+            .collect();
+        entries.sort();
+
+        assert_eq!(
+            entries,
+            vec![
+                ("snap/42/cgroup".to_string(), None),
+                ("snap/42/exe".to_string(), Some("/usr/bin/app".to_string())),
+                ("snap/42/fd/3".to_string(), Some("socket:[777]".to_string())),
+                ("snap/42/fdinfo/3".to_string(), None),
+                ("snap/42/net/tcp".to_string(), None),
+                (
+                    "snap/42/ns/net".to_string(),
+                    Some("net:[4026532300]".to_string())
+                ),
+                ("snap/42/stat".to_string(), None),
+                ("snap/etc/cron.d/backup".to_string(), None),
+                ("snap/etc/passwd".to_string(), None),
+                ("snap/etc/service/app/run".to_string(), None),
+                ("snap/net/tcp".to_string(), None),
+                ("snap/run/named/named.pid".to_string(), None),
+                ("snap/run/systemd/inhibit/1".to_string(), None),
+                ("snap/stat".to_string(), None),
+                ("snap/sys/fs/cgroup/app.slice/memory.max".to_string(), None),
+            ]
+        );
+    }
+}
@@ -0,0 +1,79 @@
+use crate::adapters::hostfs::HostFs;
+use crate::adapters::proc::ProcFs;
+use crate::adapters::snapshot::SystemSnapshot;
+use crate::adapters::system::RealSystem;
+use crate::core::models::{Enrich, PathHolder, PathQuery, Process, RemoteFilter, SocketOwner};
+use crate::core::ports::{SystemError, SystemProvider};
+use std::path::PathBuf;
+
+/// Replays a directory laid out like `/proc`, such as one recorded with
+/// `witr-rs capture`. The Linux parsers run against the tree exactly as
+/// they would against the live host, but nothing outside it is consulted:
+/// host files such as `etc/passwd` are read from the same directory.
+pub struct FixtureSystem {
+    inner: RealSystem,
+}
+
+impl FixtureSystem {
+    pub fn load(root: impl Into<PathBuf>) -> Result<Self, SystemError> {
+        let root = root.into();
+        let procfs = ProcFs::new(&root);
+        if procfs.pids().is_empty() {
+            return Err(SystemError::Unknown(format!(
+                "{} contains no process directories",
+                procfs.root().display()
+            )));
+        }
+        Ok(Self {
+            inner: RealSystem::offline(
+                SystemSnapshot::from_procfs(procfs).with_host(HostFs::new(root)),
+            ),
+        })
+    }
+}
+
+impl SystemProvider for FixtureSystem {
+    fn get_process_by_pid(&self, pid: u32, enrich: Enrich) -> Result<Process, SystemError> {
+        self.inner.get_process_by_pid(pid, enrich)
+    }
+
+    fn find_processes_by_name(
+        &self,
+        name: &str,
+        enrich: Enrich,
+    ) -> Result<Vec<Process>, SystemError> {
+        self.inner.find_processes_by_name(name, enrich)
+    }
+
+    fn find_processes_by_port(
+        &self,
+        port: u16,
+        enrich: Enrich,
+    ) -> Result<Vec<SocketOwner>, SystemError> {
+        self.inner.find_processes_by_port(port, enrich)
+    }
+
+    fn find_processes_by_remote(
+        &self,
+        remote: &RemoteFilter,
+        enrich: Enrich,
+    ) -> Result<Vec<SocketOwner>, SystemError> {
+        self.inner.find_processes_by_remote(remote, enrich)
+    }
+
+    fn find_processes_by_path(
+        &self,
+        query: &PathQuery,
+        enrich: Enrich,
+    ) -> Result<Vec<PathHolder>, SystemError> {
+        self.inner.find_processes_by_path(query, enrich)
+    }
+
+    fn get_all_pids(&self) -> Result<Vec<u32>, SystemError> {
+        self.inner.get_all_pids()
+    }
+
+    fn refresh(&self) -> Result<(), SystemError> {
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+use crate::adapters::proc::ProcFs;
+use std::path::{Path, PathBuf};
+
+/// Root of the host filesystem that files such as `/etc/passwd` are read
+/// from. Lets witr-rs inspect a host whose root is mounted elsewhere, e.g.
+/// at `/host` inside a privileged sidecar container.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostFs {
+    root: PathBuf,
+}
+
+impl Default for HostFs {
+    fn default() -> Self {
+        Self::new("/")
+    }
+}
+
+impl HostFs {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Maps an absolute host path such as `/etc/passwd` under the root.
+    pub fn path(&self, abs: impl AsRef<Path>) -> PathBuf {
+        let abs = abs.as_ref();
+        self.root.join(abs.strip_prefix("/").unwrap_or(abs))
+    }
+
+    /// Whether this is the root of the machine witr-rs runs on, so local
+    /// tools such as `systemctl` describe it.
+    pub fn is_local(&self) -> bool {
+        self.root == Path::new("/")
+    }
+
+    /// The host's procfs, assuming it is mounted at `<root>/proc`.
+    pub fn proc_fs(&self) -> ProcFs {
+        ProcFs::new(self.path("/proc"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paths() {
+        let host = HostFs::new("/host");
+        assert_eq!(host.path("/etc/passwd"), PathBuf::from("/host/etc/passwd"));
+        assert_eq!(host.proc_fs(), ProcFs::new("/host/proc"));
+        assert_eq!(
+            HostFs::default().path("/etc/passwd"),
+            PathBuf::from("/etc/passwd")
+        );
+        assert_eq!(HostFs::default().proc_fs(), ProcFs::default());
+        assert!(HostFs::default().is_local());
+        assert!(!host.is_local());
+    }
+}
@@ -1,6 +1,16 @@
+#[cfg(target_os = "linux")]
+pub mod capture;
+#[cfg(target_os = "linux")]
+pub mod fixture;
+pub mod hostfs;
 pub mod network;
 pub mod proc;
+pub mod snapshot;
 pub mod source;
 pub mod system;
+#[cfg(test)]
+mod testutil;
 
+#[cfg(target_os = "linux")]
+pub use fixture::FixtureSystem;
 pub use system::RealSystem;
@@ -1,7 +1,8 @@
+use crate::adapters::proc::ProcFs;
 use std::fs;
 
-pub fn get_boot_time() -> u64 {
-    if let Ok(content) = fs::read_to_string("/proc/stat") {
+pub fn get_boot_time(procfs: &ProcFs) -> u64 {
+    if let Ok(content) = fs::read_to_string(procfs.path("stat")) {
         return parse_boot_time(&content);
     }
     0
@@ -0,0 +1,206 @@
+use crate::adapters::hostfs::HostFs;
+use crate::core::models::{CgroupInfo, CgroupKind, CgroupLevel};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Memory limits at or above this are the kernel's "unlimited" sentinel.
+const UNLIMITED: u64 = 1 << 62;
+
+/// One line of `/proc/<pid>/cgroup`: `hierarchy-id:controllers:path`.
+struct Membership<'a> {
+    hierarchy: u32,
+    controllers: Vec<&'a str>,
+    path: &'a str,
+}
+
+/// The process's path in the unified (v2) hierarchy, from the `0::` line of
+/// `/proc/<pid>/cgroup`.
+pub fn unified_path(content: &str) -> Option<&str> {
+    content
+        .lines()
+        .find_map(|line| line.strip_prefix("0::"))
+        .map(str::trim)
+}
+
+/// Directory of a v2 cgroup under the host's unified mount: `/sys/fs/cgroup`,
+/// or `/sys/fs/cgroup/unified` on hybrid hosts.
+pub fn cgroup_dir(host: &HostFs, path: &str) -> PathBuf {
+    let root = host.path("/sys/fs/cgroup");
+    let unified = root.join("unified");
+    let root = if !root.join("cgroup.controllers").exists() && unified.exists() {
+        unified
+    } else {
+        root
+    };
+    root.join(path.trim_start_matches('/'))
+}
+
+/// A cgroup interface file, trimmed; `None` when the controller is not
+/// enabled for the group.
+pub fn read_file(dir: &Path, name: &str) -> Option<String> {
+    fs::read_to_string(dir.join(name))
+        .ok()
+        .map(|s| s.trim().to_string())
+}
+
+/// Resolves the contents of `/proc/<pid>/cgroup` into the chain of slices
+/// and units above the process, with the limits set at each level. The
+/// unified hierarchy is preferred; on legacy hosts systemd's named
+/// hierarchy gives the unit path and each controller's own hierarchy the
+/// limits.
+pub fn get_cgroup_info(host: &HostFs, content: &str) -> Option<CgroupInfo> {
+    let memberships = parse_memberships(content);
+    let unified = memberships
+        .iter()
+        .find(|m| m.hierarchy == 0 && m.path != "/");
+    let (version, path) = match unified {
+        Some(m) => (2, m.path),
+        None => (1, legacy_path(&memberships)?),
+    };
+
+    let mut levels = Vec::new();
+    let mut prefix = String::new();
+    for name in path.split('/').filter(|s| !s.is_empty()) {
+        prefix = format!("{}/{}", prefix, name);
+        let (memory_max, cpu_max, pids_max) = if version == 2 {
+            v2_limits(&cgroup_dir(host, &prefix))
+        } else {
+            v1_limits(host, &memberships, &prefix)
+        };
+        levels.push(CgroupLevel {
+            name: name.to_string(),
+            kind: CgroupKind::from_name(name),
+            memory_max,
+            cpu_max,
+            pids_max,
+        });
+    }
+    Some(CgroupInfo {
+        version,
+        path: path.to_string(),
+        levels,
+    })
+}
+
+fn parse_memberships(content: &str) -> Vec<Membership<'_>> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.splitn(3, ':');
+            let hierarchy = parts.next()?.parse().ok()?;
+            let controllers = parts.next()?.split(',').filter(|c| !c.is_empty());
+            Some(Membership {
+                hierarchy,
+                controllers: controllers.collect(),
+                path: parts.next()?.trim(),
+            })
+        })
+        .collect()
+}
+
+// systemd's `name=systemd` hierarchy mirrors its units; without it, any
+// controller's path is the best available.
+fn legacy_path<'a>(memberships: &[Membership<'a>]) -> Option<&'a str> {
+    memberships
+        .iter()
+        .find(|m| m.controllers.contains(&"name=systemd"))
+        .or_else(|| memberships.iter().find(|m| m.hierarchy != 0))
+        .map(|m| m.path)
+}
+
+fn v2_limits(dir: &Path) -> (Option<u64>, Option<f64>, Option<u64>) {
+    (
+        read_file(dir, "memory.max").and_then(|v| v.parse().ok()),
+        read_file(dir, "cpu.max").and_then(|v| {
+            let (quota, period) = v.split_once(' ')?;
+            cpu_quota(quota.parse().ok()?, period.parse().ok()?)
+        }),
+        read_file(dir, "pids.max").and_then(|v| v.parse().ok()),
+    )
+}
+
+// A controller's limits apply at `prefix` only if its own hierarchy places
+// the process at or below that path.
+fn v1_limits(
+    host: &HostFs,
+    memberships: &[Membership],
+    prefix: &str,
+) -> (Option<u64>, Option<f64>, Option<u64>) {
+    let dir = |controller: &str| {
+        memberships
+            .iter()
+            .find(|m| m.controllers.contains(&controller))
+            .filter(|m| Path::new(m.path).starts_with(prefix))
+            .map(|_| {
+                host.path("/sys/fs/cgroup")
+                    .join(controller)
+                    .join(prefix.trim_start_matches('/'))
+            })
+    };
+    let value = |controller: &str, file: &str| {
+        dir(controller)
+            .and_then(|d| read_file(&d, file))
+            .and_then(|v| v.parse::<i64>().ok())
+    };
+    let memory_max = value("memory", "memory.limit_in_bytes")
+        .map(|v| v as u64)
+        .filter(|&v| v < UNLIMITED);
+    let cpu_max = value("cpu", "cpu.cfs_quota_us")
+        .zip(value("cpu", "cpu.cfs_period_us"))
+        .and_then(|(quota, period)| cpu_quota(quota, period));
+    let pids_max = value("pids", "pids.max").map(|v| v as u64);
+    (memory_max, cpu_max, pids_max)
+}
+
+// v1 writes an unlimited quota as -1; v2 writes `max`, which fails to parse.
+fn cpu_quota(quota: i64, period: i64) -> Option<f64> {
+    (quota > 0 && period > 0).then(|| quota as f64 / period as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::testutil::TempTree;
+
+    #[test]
+    fn test_unified_path() {
+        let v2 = "0::/system.slice/nginx.service\n";
+        assert_eq!(unified_path(v2), Some("/system.slice/nginx.service"));
+
+        let hybrid = "9:name=systemd:/\n4:memory:/user.slice\n0::/user.slice/session-3.scope\n";
+        assert_eq!(unified_path(hybrid), Some("/user.slice/session-3.scope"));
+        assert_eq!(unified_path("4:memory:/user.slice\n"), None);
+        assert_eq!(
+            cgroup_dir(&HostFs::new("/host"), "/system.slice/a.service"),
+            PathBuf::from("/host/sys/fs/cgroup/system.slice/a.service")
+        );
+    }
+
+    #[test]
+    fn test_cgroup_info_from_legacy_hierarchy() {
+        let content = "\
+12:pids:/user.slice/user-1000.slice/session-3.scope
+4:memory:/user.slice/user-1000.slice
+1:name=systemd:/user.slice/user-1000.slice/session-3.scope
+0::/
+";
+        let tree = TempTree::new("cgroup");
+        let memory = "sys/fs/cgroup/memory/user.slice";
+        tree.write(
+            &format!("{}/user-1000.slice/memory.limit_in_bytes", memory),
+            "1073741824\n",
+        )
+        .write(
+            &format!("{}/memory.limit_in_bytes", memory),
+            "9223372036854771712\n",
+        );
+
+        let info = get_cgroup_info(&tree.host(), content).unwrap();
+        assert_eq!(info.version, 1);
+        let names: Vec<&str> = info.levels.iter().map(|l| l.name.as_str()).collect();
+        assert_eq!(names, ["user.slice", "user-1000.slice", "session-3.scope"]);
+        let limits: Vec<Option<u64>> = info.levels.iter().map(|l| l.memory_max).collect();
+        assert_eq!(limits, [None, Some(1 << 30), None]);
+        assert_eq!(info.owner().unwrap().kind, CgroupKind::Session);
+    }
+}
@@ -1,8 +1,16 @@
+use crate::adapters::proc::ProcFs;
 use std::fs;
 
-pub fn get_cmdline(pid: u32) -> Vec<String> {
-    let cmdline_path = format!("/proc/{}/cmdline", pid);
-    if let Ok(content) = fs::read(&cmdline_path) {
+pub fn get_cmdline(procfs: &ProcFs, pid: u32) -> Vec<String> {
+    if let Ok(content) = fs::read(procfs.pid_path(pid, "cmdline")) {
+        return parse_cmdline(&content);
+    }
+    Vec::new()
+}
+
+/// Same NUL-separated layout as `cmdline`.
+pub fn get_environ(procfs: &ProcFs, pid: u32) -> Vec<String> {
+    if let Ok(content) = fs::read(procfs.pid_path(pid, "environ")) {
         return parse_cmdline(&content);
     }
     Vec::new()
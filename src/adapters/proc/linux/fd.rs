@@ -1,8 +1,9 @@
+use crate::adapters::proc::ProcFs;
 use std::fs;
 
-pub fn get_open_fds(pid: u32) -> Vec<u64> {
+pub fn get_open_fds(procfs: &ProcFs, pid: u32) -> Vec<u64> {
     let mut fds = Vec::new();
-    let fd_dir = format!("/proc/{}/fd", pid);
+    let fd_dir = procfs.pid_path(pid, "fd");
 
     if let Ok(entries) = fs::read_dir(&fd_dir) {
         for entry in entries.flatten() {
@@ -16,8 +17,8 @@ pub fn get_open_fds(pid: u32) -> Vec<u64> {
     fds
 }
 
-pub fn count_open_files(pid: u32) -> usize {
-    get_open_fds(pid).len()
+pub fn count_open_files(procfs: &ProcFs, pid: u32) -> usize {
+    get_open_fds(procfs, pid).len()
 }
 
 #[allow(dead_code)]
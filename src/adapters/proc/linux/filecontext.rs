@@ -1,32 +1,186 @@
+use super::fd::get_open_fds;
+use super::maps::get_mapped_files;
+use crate::adapters::proc::ProcFs;
+use crate::core::models::{FileContext, FileKind, HeldPath, OpenFile, PathUse};
 use std::fs;
 
-pub fn get_open_files(_pid: u32) -> Vec<String> {
-    Vec::new()
+/// Every open descriptor of `pid`, with the details fdinfo provides, plus
+/// locks from `/proc/locks` and inotify watches. `None` when the fd table
+/// cannot be read, usually for lack of permission.
+pub fn get_file_context(procfs: &ProcFs, pid: u32) -> Option<FileContext> {
+    fs::read_dir(procfs.pid_path(pid, "fd")).ok()?;
+
+    let mut fds = Vec::new();
+    let mut watched_dirs = Vec::new();
+    for fd in get_open_fds(procfs, pid) {
+        let Some(target) = fs::read_link(procfs.pid_path(pid, &format!("fd/{}", fd))).ok() else {
+            continue;
+        };
+        let target = target.to_string_lossy().to_string();
+        let fdinfo =
+            fs::read_to_string(procfs.pid_path(pid, &format!("fdinfo/{}", fd))).unwrap_or_default();
+        let (pos, flags, inode) = parse_fdinfo(&fdinfo);
+        let kind = FileKind::from_target(&target);
+        if kind == FileKind::AnonInode("inotify".to_string()) {
+            watched_dirs.extend(parse_inotify_watches(&fdinfo));
+        }
+        fds.push(OpenFile {
+            fd: fd as u32,
+            kind,
+            target,
+            pos,
+            flags,
+            inode,
+        });
+    }
+    fds.sort_by_key(|f| f.fd);
+
+    let locks = fs::read_to_string(procfs.path("locks")).unwrap_or_default();
+    let locked_files = parse_locks(&locks, pid)
+        .into_iter()
+        .map(|(inode, lock)| {
+            let file = fds.iter().find(|f| f.inode == Some(inode));
+            match file {
+                Some(file) => format!("{} ({})", file.target, lock),
+                None => format!("inode {} ({})", inode, lock),
+            }
+        })
+        .collect();
+
+    let (file_limit, file_limit_hard) = fs::read_to_string(procfs.pid_path(pid, "limits"))
+        .ok()
+        .and_then(|limits| parse_file_limit(&limits))
+        .unwrap_or_default();
+
+    Some(FileContext {
+        open_files: fds.len() as u32,
+        file_limit,
+        file_limit_hard,
+        fds,
+        locked_files,
+        watched_dirs,
+    })
+}
+
+/// Open descriptors and the soft limit on them.
+pub fn get_file_limit(procfs: &ProcFs, pid: u32) -> Option<(u64, u64)> {
+    let content = fs::read_to_string(procfs.pid_path(pid, "limits")).ok()?;
+    let (soft, _) = parse_file_limit(&content)?;
+    Some((get_open_fds(procfs, pid).len() as u64, soft))
 }
 
-pub fn get_file_limit(pid: u32) -> Option<(u64, u64)> {
-    let limits_path = format!("/proc/{}/limits", pid);
-    if let Ok(content) = fs::read_to_string(&limits_path) {
-        return parse_file_limit(&content);
+/// Every path `pid` keeps in use: open files, its working and root
+/// directories, its executable and other mapped files.
+pub fn get_held_paths(procfs: &ProcFs, pid: u32) -> Vec<HeldPath> {
+    let link = |rel: &str| {
+        fs::read_link(procfs.pid_path(pid, rel))
+            .ok()
+            .map(|p| p.to_string_lossy().to_string())
+    };
+    let mut fds = get_open_fds(procfs, pid);
+    fds.sort();
+
+    let mut held: Vec<HeldPath> = fds
+        .into_iter()
+        .filter_map(|fd| Some((fd, link(&format!("fd/{}", fd))?)))
+        .filter(|(_, target)| FileKind::from_target(target) == FileKind::File)
+        .map(|(fd, path)| HeldPath {
+            usage: PathUse::Fd(fd as u32),
+            path,
+        })
+        .collect();
+    let exe = link("exe");
+    for (usage, path) in [
+        (PathUse::Cwd, link("cwd")),
+        (PathUse::Root, link("root")),
+        (PathUse::Exe, exe.clone()),
+    ] {
+        if let Some(path) = path {
+            held.push(HeldPath { usage, path });
+        }
     }
-    None
+    for path in get_mapped_files(procfs, pid) {
+        if exe.as_ref() != Some(&path) {
+            held.push(HeldPath {
+                usage: PathUse::Mmap,
+                path,
+            });
+        }
+    }
+    held
 }
 
+/// `(soft, hard)` limits on open files.
 fn parse_file_limit(content: &str) -> Option<(u64, u64)> {
     for line in content.lines() {
         if line.contains("Max open files") {
             let parts: Vec<&str> = line.split_whitespace().collect();
             if parts.len() >= 5 {
-                if let (Ok(soft), Ok(_hard)) = (parts[3].parse::<u64>(), parts[4].parse::<u64>()) {
-                    let current = 0; // Placeholder
-                    return Some((current, soft));
-                }
+                return Some((parts[3].parse().ok()?, parts[4].parse().ok()?));
             }
         }
     }
     None
 }
 
+/// `(pos, flags, inode)`; flags are printed in octal.
+fn parse_fdinfo(content: &str) -> (u64, u32, Option<u64>) {
+    let field = |key: &str| {
+        content
+            .lines()
+            .find_map(|l| l.strip_prefix(key)?.strip_prefix(':'))
+            .map(str::trim)
+    };
+    (
+        field("pos").and_then(|v| v.parse().ok()).unwrap_or(0),
+        field("flags")
+            .and_then(|v| u32::from_str_radix(v, 8).ok())
+            .unwrap_or(0),
+        field("ino").and_then(|v| v.parse().ok()),
+    )
+}
+
+// Lines look like `1: POSIX  ADVISORY  WRITE 812 fd:01:131074 0 EOF`; the
+// last part of the device field is the inode, in decimal. Blocked waiters
+// are marked with `->` and hold nothing yet.
+fn parse_locks(content: &str, pid: u32) -> Vec<(u64, String)> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.split_whitespace().skip(1).collect();
+            if parts.len() < 5 || parts[0] == "->" || parts[3] != pid.to_string() {
+                return None;
+            }
+            let inode = parts[4].rsplit(':').next()?.parse().ok()?;
+            Some((inode, format!("{} {}", parts[0], parts[2])))
+        })
+        .collect()
+}
+
+// `inotify wd:1 ino:2a0003 sdev:800001 mask:...`: inode and device in hex,
+// the device in the kernel's internal encoding (12-bit major, 20-bit minor).
+// The watch does not record a path, so it is reported by device and inode.
+fn parse_inotify_watches(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let rest = line.strip_prefix("inotify ")?;
+            let field = |key: &str| {
+                rest.split_whitespace()
+                    .find_map(|f| f.strip_prefix(key))
+                    .and_then(|v| u64::from_str_radix(v, 16).ok())
+            };
+            let (inode, dev) = (field("ino:")?, field("sdev:")?);
+            Some(format!(
+                "dev {}:{} inode {}",
+                dev >> 20,
+                dev & 0xfffff,
+                inode
+            ))
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -52,10 +206,33 @@ Max nice priority         0                    0
 Max realtime priority     0                    0                    
 Max realtime timeout      unlimited            unlimited            us        
 ";
-        let result = parse_file_limit(content);
-        assert!(result.is_some());
-        let (current, limit) = result.unwrap();
-        assert_eq!(current, 0);
-        assert_eq!(limit, 1024);
+        assert_eq!(parse_file_limit(content), Some((1024, 1048576)));
+    }
+
+    #[test]
+    fn test_parse_fdinfo() {
+        let fdinfo = "pos:\t4096\nflags:\t0102002\nmnt_id:\t29\nino:\t131074\n";
+        assert_eq!(parse_fdinfo(fdinfo), (4096, 0o102002, Some(131074)));
+        assert_eq!(parse_fdinfo(""), (0, 0, None));
+    }
+
+    #[test]
+    fn test_parse_locks() {
+        let locks = "1: POSIX  ADVISORY  WRITE 812 fd:01:131074 0 EOF
+2: FLOCK  ADVISORY  WRITE 900 00:19:2046 0 EOF
+2: -> FLOCK  ADVISORY  WRITE 812 00:19:2046 0 EOF
+3: OFDLCK ADVISORY  READ  -1 00:06:9 0 EOF
+";
+        assert_eq!(
+            parse_locks(locks, 812),
+            vec![(131074, "POSIX WRITE".to_string())]
+        );
+        assert!(parse_locks(locks, 1).is_empty());
+    }
+
+    #[test]
+    fn test_parse_inotify_watches() {
+        let fdinfo = "pos:\t0\nflags:\t02004000\nmnt_id:\t15\ninotify wd:1 ino:2a0003 sdev:800001 mask:fc6 ignored_mask:0 fhandle-bytes:8 fhandle-type:1 f_handle:03002a00a5f9c4e7\n";
+        assert_eq!(parse_inotify_watches(fdinfo), vec!["dev 8:1 inode 2752515"]);
     }
 }
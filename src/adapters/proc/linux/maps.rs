@@ -0,0 +1,129 @@
+use crate::adapters::proc::ProcFs;
+use crate::core::models::MemoryContext;
+use std::fs;
+
+struct Mapping<'a> {
+    range: &'a str,
+    perms: &'a str,
+    path: &'a str,
+}
+
+/// Files mapped into the address space of `pid`, each listed once.
+pub fn get_mapped_files(procfs: &ProcFs, pid: u32) -> Vec<String> {
+    let content = fs::read_to_string(procfs.pid_path(pid, "maps")).unwrap_or_default();
+    let mut files: Vec<String> = Vec::new();
+    for m in parse_maps(&content) {
+        if m.path.starts_with('/') && !files.iter().any(|f| f == m.path) {
+            files.push(m.path.to_string());
+        }
+    }
+    files
+}
+
+/// Memory usage from `smaps_rollup` and the executable mappings from
+/// `maps`. `None` when the maps cannot be read, usually for lack of
+/// permission.
+pub fn get_memory_context(procfs: &ProcFs, pid: u32) -> Option<MemoryContext> {
+    let maps = fs::read_to_string(procfs.pid_path(pid, "maps")).ok()?;
+    let exe = fs::read_link(procfs.pid_path(pid, "exe"))
+        .ok()
+        .map(|p| p.to_string_lossy().to_string());
+    let rollup = fs::read_to_string(procfs.pid_path(pid, "smaps_rollup")).unwrap_or_default();
+    let kb = |key: &str| rollup_field(&rollup, key) * 1024;
+
+    let mut ctx = MemoryContext {
+        rss: kb("Rss"),
+        pss: kb("Pss"),
+        pss_anon: kb("Pss_Anon"),
+        pss_file: kb("Pss_File"),
+        shared: kb("Shared_Clean") + kb("Shared_Dirty"),
+        private: kb("Private_Clean") + kb("Private_Dirty"),
+        swap: kb("Swap"),
+        ..Default::default()
+    };
+    for m in parse_maps(&maps).filter(|m| m.perms.contains('x')) {
+        if m.path.is_empty() || m.path.starts_with("/memfd:") {
+            ctx.anon_exec.push(
+                format!("{} {} {}", m.range, m.perms, m.path)
+                    .trim_end()
+                    .to_string(),
+            );
+        } else if m.path.starts_with('/')
+            && exe.as_deref() != Some(m.path)
+            && !ctx.libraries.iter().any(|l| l == m.path)
+        {
+            ctx.libraries.push(m.path.to_string());
+        }
+    }
+    Some(ctx)
+}
+
+// Lines are `start-end perms offset dev inode [path]`; anonymous regions have
+// no path and special ones such as `[heap]` or `[vdso]` are not files.
+fn parse_maps(content: &str) -> impl Iterator<Item = Mapping<'_>> {
+    content.lines().filter_map(|line| {
+        let mut fields = line.splitn(6, ' ');
+        let range = fields.next()?;
+        let perms = fields.next()?;
+        let path = fields.nth(3).map(str::trim).unwrap_or_default();
+        Some(Mapping { range, perms, path })
+    })
+}
+
+fn rollup_field(content: &str, key: &str) -> u64 {
+    content
+        .lines()
+        .find_map(|l| l.strip_prefix(key)?.strip_prefix(':'))
+        .and_then(|v| v.trim().trim_end_matches(" kB").parse().ok())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAPS: &str = "\
+5600e3a7e000-5600e3a80000 r--p 00000000 fe:00 317563                     /usr/bin/cat
+5600e3a80000-5600e3a85000 r-xp 00002000 fe:00 317563                     /usr/bin/cat
+5600e4a00000-5600e4a21000 rw-p 00000000 00:00 0                          [heap]
+7f2a1c000000-7f2a1c021000 rwxp 00000000 00:00 0
+7f2a1d200000-7f2a1d228000 r-xp 00000000 fe:00 263224                     /usr/lib/libc.so.6
+7f2a1d400000-7f2a1d401000 rw-s 00000000 00:01 1024                       /dev/shm/my cache (deleted)
+7ffd3b9e1000-7ffd3b9e3000 r-xp 00000000 00:00 0                          [vdso]
+";
+
+    #[test]
+    fn test_parse_maps() {
+        let paths: Vec<&str> = parse_maps(MAPS).map(|m| m.path).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "/usr/bin/cat",
+                "/usr/bin/cat",
+                "[heap]",
+                "",
+                "/usr/lib/libc.so.6",
+                "/dev/shm/my cache (deleted)",
+                "[vdso]"
+            ]
+        );
+        let exec: Vec<&str> = parse_maps(MAPS)
+            .filter(|m| m.perms.contains('x') && m.path.is_empty())
+            .map(|m| m.range)
+            .collect();
+        assert_eq!(exec, vec!["7f2a1c000000-7f2a1c021000"]);
+    }
+
+    #[test]
+    fn test_rollup_field() {
+        let rollup = "\
+55d4c8a00000-7ffd3ba02000 ---p 00000000 00:00 0                          [rollup]
+Rss:               12288 kB
+Pss:                8192 kB
+Pss_Anon:           6144 kB
+";
+        assert_eq!(rollup_field(rollup, "Rss"), 12288);
+        assert_eq!(rollup_field(rollup, "Pss"), 8192);
+        assert_eq!(rollup_field(rollup, "Swap"), 0);
+    }
+}
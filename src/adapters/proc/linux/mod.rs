@@ -1,10 +1,15 @@
 pub mod boot;
+pub mod cgroup;
 pub mod cmdline;
 pub mod fd;
 pub mod filecontext;
+pub mod maps;
 pub mod net;
+pub mod netlink;
+pub mod netns;
 pub use net::*;
 pub mod process;
 pub mod resource;
 pub mod socketstate;
+pub mod stat;
 pub mod user;
@@ -1,18 +1,22 @@
+use super::netns::foreign_namespaces;
+use super::socketstate::{get_all_socket_states, tcp_state_name};
+use crate::adapters::proc::{ProcFs, SocketTable};
 use crate::core::models::SocketInfo;
 use std::collections::HashMap;
 use std::fs;
 
-pub fn get_listening_sockets() -> HashMap<u64, SocketInfo> {
+pub fn get_listening_sockets(procfs: &ProcFs) -> HashMap<u64, SocketInfo> {
     let mut sockets = HashMap::new();
 
-    if let Ok(tcp) = fs::read_to_string("/proc/net/tcp") {
+    if let Ok(tcp) = fs::read_to_string(procfs.path("net/tcp")) {
         sockets.extend(parse_tcp_file(&tcp));
     }
 
-    if let Ok(tcp6) = fs::read_to_string("/proc/net/tcp6") {
+    if let Ok(tcp6) = fs::read_to_string(procfs.path("net/tcp6")) {
         sockets.extend(parse_tcp_file(&tcp6));
     }
 
+    sockets.retain(|_, s| s.is_listening());
     sockets
 }
 
@@ -26,7 +30,7 @@ fn parse_tcp_file(content: &str) -> HashMap<u64, SocketInfo> {
                     if let Ok(inode) = parts[9].parse::<u64>() {
                         let info = SocketInfo::new(
                             port,
-                            "LISTEN".to_string(),
+                            tcp_state_name(parts[3]).to_string(),
                             ip.to_string(),
                             "0.0.0.0:0".to_string(),
                         );
@@ -69,9 +73,9 @@ pub fn parse_ip_port(hex_str: &str) -> Option<(std::net::IpAddr, u16)> {
     Some((ip, port))
 }
 
-pub fn get_sockets_for_pid(pid: u32) -> Vec<u64> {
+pub fn get_sockets_for_pid(procfs: &ProcFs, pid: u32) -> Vec<u64> {
     let mut inodes = Vec::new();
-    let fd_dir = format!("/proc/{}/fd", pid);
+    let fd_dir = procfs.pid_path(pid, "fd");
 
     if let Ok(entries) = fs::read_dir(&fd_dir) {
         for entry in entries.flatten() {
@@ -87,6 +91,44 @@ pub fn get_sockets_for_pid(pid: u32) -> Vec<u64> {
     inodes
 }
 
+/// Sockets are read once per network namespace: the host's from the
+/// top-level tables, every other one from a member's `/proc/<pid>/net`.
+/// Socket inodes are unique across namespaces, so one map holds them all.
+pub fn get_socket_table(procfs: &ProcFs, pids: &[u32]) -> SocketTable {
+    let mut sockets = get_all_socket_states(procfs);
+    for (ns, members) in foreign_namespaces(procfs, pids) {
+        // A member may have exited since the scan; any other will do.
+        let mut states = members
+            .iter()
+            .map(|&pid| get_all_socket_states(&procfs.pid_root(pid)))
+            .find(|states| !states.is_empty())
+            .unwrap_or_default();
+        for socket in states.values_mut() {
+            socket.netns = Some(ns);
+        }
+        sockets.extend(states);
+    }
+    let mut pid_inodes = HashMap::new();
+
+    for &pid in pids {
+        let mut inodes: Vec<u64> = get_sockets_for_pid(procfs, pid)
+            .into_iter()
+            .filter(|inode| sockets.contains_key(inode))
+            .collect();
+        // A socket dup'ed onto several fds is still one socket.
+        inodes.sort_unstable();
+        inodes.dedup();
+        if !inodes.is_empty() {
+            pid_inodes.insert(pid, inodes);
+        }
+    }
+
+    SocketTable {
+        sockets,
+        pid_inodes,
+    }
+}
+
 fn parse_socket_link(link: &str) -> Option<u64> {
     if link.starts_with("socket:[") {
         if let Some(inode_str) = link
@@ -109,9 +151,12 @@ mod tests {
         let content_v4 = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode                                                     
    0: 00000000:1F90 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 21623 1 0000000000000000 100 0 0 10 0
    1: 0100007F:0277 00000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 24159 1 0000000000000000 100 0 0 10 0
+   2: 0100007F:1F90 0100007F:C350 01 00000000:00000000 00:00000000 00000000  1000        0 24160 1 0000000000000000 100 0 0 10 0
 ";
         let sockets = parse_tcp_file(content_v4);
-        assert_eq!(sockets.len(), 2);
+        assert_eq!(sockets.len(), 3);
+        assert_eq!(sockets[&21623].state, "LISTEN");
+        assert_eq!(sockets[&24160].state, "ESTABLISHED");
 
         // 0.0.0.0:8080
         let s1 = sockets.get(&21623).unwrap();
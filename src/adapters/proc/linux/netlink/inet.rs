@@ -0,0 +1,91 @@
+use super::{attributes, read_u32, read_u64};
+use crate::adapters::proc::linux::socketstate::state_name;
+use crate::core::models::{SocketInfo, TcpInfo};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+const AF_INET: u8 = libc::AF_INET as u8;
+const AF_INET6: u8 = libc::AF_INET6 as u8;
+const IPPROTO_TCP: u8 = libc::IPPROTO_TCP as u8;
+const IPPROTO_UDP: u8 = libc::IPPROTO_UDP as u8;
+
+pub const TABLES: &[(u8, u8, &str)] = &[
+    (AF_INET, IPPROTO_TCP, "tcp"),
+    (AF_INET6, IPPROTO_TCP, "tcp"),
+    (AF_INET, IPPROTO_UDP, "udp"),
+    (AF_INET6, IPPROTO_UDP, "udp"),
+];
+
+const INET_DIAG_INFO: u16 = 2;
+// struct inet_diag_req_v2 and struct inet_diag_msg.
+const REQ_LEN: usize = 56;
+const MSG_LEN: usize = 72;
+// Offsets of the fields read from struct tcp_info.
+const TCPI_UNACKED: usize = 24;
+const TCPI_LOST: usize = 32;
+const TCPI_RTT: usize = 68;
+const TCPI_RTTVAR: usize = 72;
+const TCPI_SND_CWND: usize = 80;
+const TCPI_TOTAL_RETRANS: usize = 100;
+const TCPI_BYTES_ACKED: usize = 120;
+const TCPI_BYTES_RECEIVED: usize = 128;
+
+/// A dump request for every socket of one family and protocol, asking for
+/// `tcp_info` as well.
+pub fn request(family: u8, protocol: u8) -> Vec<u8> {
+    let mut body = [0u8; REQ_LEN];
+    body[0] = family;
+    body[1] = protocol;
+    body[2] = 1 << (INET_DIAG_INFO - 1);
+    body[4..8].copy_from_slice(&u32::MAX.to_ne_bytes());
+    super::message(&body)
+}
+
+pub fn parse_message(payload: &[u8], protocol: &str) -> Option<(u64, SocketInfo)> {
+    let header = payload.get(..MSG_LEN)?;
+    let family = header[0];
+    let sport = u16::from_be_bytes([header[4], header[5]]);
+    let dport = u16::from_be_bytes([header[6], header[7]]);
+    let src = address(family, &header[8..24])?;
+    let dst = address(family, &header[24..40])?;
+    let state = state_name(&format!("{:02X}", header[1]), protocol);
+
+    let mut info = SocketInfo::new(
+        sport,
+        state.to_string(),
+        format!("{}:{}", src, sport),
+        format!("{}:{}", dst, dport),
+    )
+    .with_protocol(protocol);
+    info.rx_queue = read_u32(header, 56)?;
+    info.tx_queue = read_u32(header, 60)?;
+    info.uid = read_u32(header, 64);
+    info.tcp_info = attributes(&payload[MSG_LEN..])
+        .find(|(kind, _)| *kind == INET_DIAG_INFO)
+        .and_then(|(_, data)| parse_tcp_info(data));
+
+    let inode = read_u32(header, 68)?;
+    Some((inode as u64, info))
+}
+
+fn address(family: u8, raw: &[u8]) -> Option<IpAddr> {
+    match family {
+        AF_INET => Some(IpAddr::V4(Ipv4Addr::from(
+            <[u8; 4]>::try_from(&raw[..4]).ok()?,
+        ))),
+        AF_INET6 => Some(IpAddr::V6(Ipv6Addr::from(<[u8; 16]>::try_from(raw).ok()?))),
+        _ => None,
+    }
+}
+
+fn parse_tcp_info(data: &[u8]) -> Option<TcpInfo> {
+    Some(TcpInfo {
+        rtt_us: read_u32(data, TCPI_RTT)?,
+        rttvar_us: read_u32(data, TCPI_RTTVAR)?,
+        snd_cwnd: read_u32(data, TCPI_SND_CWND)?,
+        unacked: read_u32(data, TCPI_UNACKED)?,
+        lost: read_u32(data, TCPI_LOST)?,
+        total_retrans: read_u32(data, TCPI_TOTAL_RETRANS)?,
+        bytes_acked: read_u64(data, TCPI_BYTES_ACKED),
+        bytes_received: read_u64(data, TCPI_BYTES_RECEIVED),
+    })
+}
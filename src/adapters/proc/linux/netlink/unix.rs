@@ -0,0 +1,63 @@
+use super::{attributes, read_u32};
+use crate::core::models::SocketInfo;
+
+const UDIAG_SHOW_NAME: u32 = 0x01;
+const UDIAG_SHOW_RQLEN: u32 = 0x10;
+const UDIAG_SHOW_UID: u32 = 0x40;
+const UNIX_DIAG_NAME: u16 = 0;
+const UNIX_DIAG_RQLEN: u16 = 4;
+const UNIX_DIAG_UID: u16 = 7;
+// struct unix_diag_req and struct unix_diag_msg.
+const REQ_LEN: usize = 24;
+const MSG_LEN: usize = 16;
+
+pub fn request() -> Vec<u8> {
+    let mut body = [0u8; REQ_LEN];
+    body[0] = libc::AF_UNIX as u8;
+    body[4..8].copy_from_slice(&u32::MAX.to_ne_bytes());
+    let show = UDIAG_SHOW_NAME | UDIAG_SHOW_RQLEN | UDIAG_SHOW_UID;
+    body[12..16].copy_from_slice(&show.to_ne_bytes());
+    super::message(&body)
+}
+
+pub fn parse_message(payload: &[u8]) -> Option<(u64, SocketInfo)> {
+    let header = payload.get(..MSG_LEN)?;
+    let inode = read_u32(header, 4)?;
+    let state = match header[2] {
+        10 => "LISTEN",
+        1 => "CONNECTED",
+        2 => "CONNECTING",
+        7 => "UNCONN",
+        _ => "UNKNOWN",
+    };
+
+    let mut path = None;
+    let mut queues = None;
+    let mut uid = None;
+    for (kind, data) in attributes(&payload[MSG_LEN..]) {
+        match kind {
+            UNIX_DIAG_NAME => path = Some(socket_path(data)),
+            UNIX_DIAG_RQLEN => queues = Some((read_u32(data, 0)?, read_u32(data, 4)?)),
+            UNIX_DIAG_UID => uid = read_u32(data, 0),
+            _ => {}
+        }
+    }
+
+    let mut info = SocketInfo::unix(path, state.to_string());
+    if let Some((rx, tx)) = queues {
+        info.rx_queue = rx;
+        info.tx_queue = tx;
+    }
+    info.uid = uid;
+    Some((inode as u64, info))
+}
+
+// Abstract socket names start with a NUL byte; /proc/net/unix shows them
+// with a leading '@', so do the same.
+fn socket_path(raw: &[u8]) -> String {
+    let name = String::from_utf8_lossy(raw);
+    match name.strip_prefix('\0') {
+        Some(abstract_name) => format!("@{}", abstract_name),
+        None => name.trim_end_matches('\0').to_string(),
+    }
+}
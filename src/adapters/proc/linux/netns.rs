@@ -0,0 +1,50 @@
+use crate::adapters::proc::ProcFs;
+use std::collections::BTreeMap;
+use std::fs;
+
+/// Network namespace inode of `pid`, from its `ns/net` link.
+pub fn get_netns(procfs: &ProcFs, pid: u32) -> Option<u64> {
+    read_netns(procfs, &pid.to_string())
+}
+
+/// The namespace the top-level `net/` tables describe: the reader's own, or
+/// for a captured tree that has no `self`, init's.
+pub fn host_netns(procfs: &ProcFs) -> Option<u64> {
+    read_netns(procfs, "self").or_else(|| get_netns(procfs, 1))
+}
+
+/// PIDs grouped by network namespace, leaving out those in the host's
+/// namespace and those whose namespace cannot be read (usually for lack of
+/// permission), which are looked up in the host tables instead.
+pub fn foreign_namespaces(procfs: &ProcFs, pids: &[u32]) -> BTreeMap<u64, Vec<u32>> {
+    let host = host_netns(procfs);
+    let mut namespaces: BTreeMap<u64, Vec<u32>> = BTreeMap::new();
+    for &pid in pids {
+        match get_netns(procfs, pid) {
+            Some(ns) if Some(ns) != host => namespaces.entry(ns).or_default().push(pid),
+            _ => {}
+        }
+    }
+    namespaces
+}
+
+fn read_netns(procfs: &ProcFs, dir: &str) -> Option<u64> {
+    let link = fs::read_link(procfs.path(dir).join("ns/net")).ok()?;
+    parse_netns_link(&link.to_string_lossy())
+}
+
+fn parse_netns_link(link: &str) -> Option<u64> {
+    link.strip_prefix("net:[")?.strip_suffix(']')?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_netns_link() {
+        assert_eq!(parse_netns_link("net:[4026531840]"), Some(4026531840));
+        assert_eq!(parse_netns_link("pid:[4026531836]"), None);
+        assert_eq!(parse_netns_link("net:[abc]"), None);
+    }
+}
@@ -1,8 +1,8 @@
+use crate::adapters::proc::ProcFs;
 use std::fs;
 
-pub fn get_process_name(pid: u32) -> Option<String> {
-    let comm_path = format!("/proc/{}/comm", pid);
-    if let Ok(content) = fs::read_to_string(&comm_path) {
+pub fn get_process_name(procfs: &ProcFs, pid: u32) -> Option<String> {
+    if let Ok(content) = fs::read_to_string(procfs.pid_path(pid, "comm")) {
         return clean_process_name(&content);
     }
     None
@@ -17,9 +17,16 @@ fn clean_process_name(content: &str) -> Option<String> {
     }
 }
 
-pub fn get_process_exe(pid: u32) -> Option<String> {
-    let exe_path = format!("/proc/{}/exe", pid);
-    fs::read_link(exe_path)
+pub fn get_process_exe(procfs: &ProcFs, pid: u32) -> Option<String> {
+    read_link(procfs, pid, "exe")
+}
+
+pub fn get_process_cwd(procfs: &ProcFs, pid: u32) -> Option<String> {
+    read_link(procfs, pid, "cwd")
+}
+
+fn read_link(procfs: &ProcFs, pid: u32, rel: &str) -> Option<String> {
+    fs::read_link(procfs.pid_path(pid, rel))
         .ok()
         .map(|p| p.to_string_lossy().into_owned())
 }
@@ -1,30 +1,130 @@
-use std::process::Command;
-
-pub fn get_resource_context(pid: u32) -> Option<String> {
-    if let Ok(output) = Command::new("systemd-inhibit")
-        .args(["--list", "--no-pager"])
-        .output()
-    {
-        let output_str = String::from_utf8_lossy(&output.stdout);
-        if is_inhibiting(pid, &output_str) {
-            return Some("sleep=true".to_string());
-        }
+use super::cgroup::{cgroup_dir, read_file, unified_path};
+use crate::adapters::hostfs::HostFs;
+use crate::adapters::proc::ProcFs;
+use crate::core::models::{CpuThrottling, Pressure, ResourceContext};
+use std::fs;
+
+/// Limits, pressure and OOM standing for `pid`, read from its v2 cgroup
+/// (given as the contents of `/proc/<pid>/cgroup`) and from logind's
+/// inhibitor state. `None` once the process has exited.
+pub fn get_resource_context(
+    procfs: &ProcFs,
+    host: &HostFs,
+    pid: u32,
+    cgroup: Option<&str>,
+) -> Option<ResourceContext> {
+    let proc_value = |name: &str| {
+        fs::read_to_string(procfs.pid_path(pid, name))
+            .ok()
+            .and_then(|s| s.trim().parse().ok())
+    };
+    let oom_score = proc_value("oom_score");
+    let oom_score_adj = proc_value("oom_score_adj");
+    oom_score.or(oom_score_adj)?;
+
+    let mut ctx = ResourceContext {
+        oom_score,
+        oom_score_adj,
+        ..Default::default()
+    };
+
+    if let Some(path) = cgroup.and_then(unified_path) {
+        let dir = cgroup_dir(host, path);
+        ctx.memory_current = read_file(&dir, "memory.current").and_then(|v| v.parse().ok());
+        ctx.memory_max = read_file(&dir, "memory.max").and_then(|v| v.parse().ok());
+        ctx.cpu_max = read_file(&dir, "cpu.max").and_then(|v| parse_cpu_max(&v));
+        ctx.cpu_throttling = read_file(&dir, "cpu.stat").and_then(|v| parse_cpu_stat(&v));
+        ctx.pressure = ["cpu", "memory", "io"]
+            .into_iter()
+            .filter_map(|res| parse_pressure(res, &read_file(&dir, &format!("{}.pressure", res))?))
+            .collect();
+        ctx.cgroup = Some(path.to_string());
     }
-    None
+
+    let inhibitors = get_inhibitors(host, pid);
+    ctx.prevents_sleep = inhibitors.iter().any(|(blocks_sleep, _)| *blocks_sleep);
+    ctx.inhibitors = inhibitors.into_iter().map(|(_, desc)| desc).collect();
+    Some(ctx)
 }
 
-fn is_inhibiting(pid: u32, output: &str) -> bool {
-    // Output format usually:
-    // WHO  UID  USER  PID  COMM  WHAT  WHY  MODE
-    // ...  ...  ...   123  ...   ...   ...  ...
-    let pid_str = pid.to_string();
-    for line in output.lines().skip(1) {
-        let parts: Vec<&str> = line.split_whitespace().collect();
-        if parts.len() >= 4 && parts.contains(&pid_str.as_str()) {
-            return true;
-        }
+/// Inhibitor locks `pid` holds, from the state logind keeps under
+/// `/run/systemd/inhibit`, so no D-Bus call or `systemd-inhibit` is needed.
+/// Each is paired with whether it blocks sleep outright.
+fn get_inhibitors(host: &HostFs, pid: u32) -> Vec<(bool, String)> {
+    let Ok(entries) = fs::read_dir(host.path("/run/systemd/inhibit")) else {
+        return Vec::new();
+    };
+    let mut inhibitors: Vec<(bool, String)> = entries
+        .flatten()
+        .filter_map(|e| fs::read_to_string(e.path()).ok())
+        .filter_map(|content| parse_inhibitor(&content, pid))
+        .collect();
+    inhibitors.sort();
+    inhibitors
+}
+
+// `KEY=value` lines; WHAT is a colon-separated list such as `sleep:idle`.
+// A `delay` lock only postpones sleep until the holder is done.
+fn parse_inhibitor(content: &str, pid: u32) -> Option<(bool, String)> {
+    let field = |key: &str| {
+        content
+            .lines()
+            .find_map(|l| l.strip_prefix(key)?.strip_prefix('='))
+    };
+    if field("PID")?.parse::<u32>().ok()? != pid {
+        return None;
     }
-    false
+    let what = field("WHAT")?;
+    let mode = field("MODE").unwrap_or("block");
+    let blocks_sleep = mode == "block" && what.split(':').any(|w| w == "sleep");
+    let what = what.replace(':', ", ");
+    let desc = match field("WHY").filter(|why| !why.is_empty()) {
+        Some(why) => format!("{} ({}): {}", what, mode, why),
+        None => format!("{} ({})", what, mode),
+    };
+    Some((blocks_sleep, desc))
+}
+
+// `$QUOTA $PERIOD` in microseconds, or `max $PERIOD` when unlimited.
+fn parse_cpu_max(content: &str) -> Option<f64> {
+    let (quota, period) = content.split_once(' ')?;
+    let quota: f64 = quota.parse().ok()?;
+    let period: f64 = period.trim().parse().ok()?;
+    (period > 0.0).then(|| quota / period)
+}
+
+fn parse_cpu_stat(content: &str) -> Option<CpuThrottling> {
+    let field = |key: &str| {
+        content.lines().find_map(|l| {
+            let (k, v) = l.split_once(' ')?;
+            (k == key).then(|| v.trim().parse().ok()).flatten()
+        })
+    };
+    Some(CpuThrottling {
+        periods: field("nr_periods")?,
+        throttled: field("nr_throttled")?,
+        throttled_usec: field("throttled_usec").unwrap_or(0),
+    })
+}
+
+// `some avg10=1.20 avg60=0.80 avg300=0.30 total=123456` and, for all but
+// system-wide CPU, a matching `full` line.
+fn parse_pressure(resource: &str, content: &str) -> Option<Pressure> {
+    let avg10 = |kind: &str| {
+        content
+            .lines()
+            .find_map(|l| l.strip_prefix(kind))
+            .and_then(|rest| {
+                rest.split_whitespace()
+                    .find_map(|f| f.strip_prefix("avg10="))
+            })
+            .and_then(|v| v.parse().ok())
+    };
+    Some(Pressure {
+        resource: resource.to_string(),
+        some: avg10("some ")?,
+        full: avg10("full "),
+    })
 }
 
 #[cfg(test)]
@@ -32,18 +132,45 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_is_inhibiting() {
-        let output = "     WHO       UID  USER  PID  COMM           WHAT                                                     WHY                                       MODE 
-GNOME Shell  1000 user 1234 gnome-shell    handle-lid-switch                                        External monitor attached or configuration block     
-Chrome       1000 user 5555 chrome         audio-playing                                            Playing Audio                             delay
-";
-        assert!(is_inhibiting(5555, output));
-        assert!(is_inhibiting(1234, output));
-        assert!(!is_inhibiting(9999, output));
+    fn test_parse_cpu_limits() {
+        assert_eq!(parse_cpu_max("50000 100000"), Some(0.5));
+        assert_eq!(parse_cpu_max("max 100000"), None);
+
+        let stat = "usage_usec 8000\nnr_periods 340\nnr_throttled 41\nthrottled_usec 912000\n";
+        assert_eq!(
+            parse_cpu_stat(stat),
+            Some(CpuThrottling {
+                periods: 340,
+                throttled: 41,
+                throttled_usec: 912000
+            })
+        );
+        assert_eq!(parse_cpu_stat("usage_usec 8000\n"), None);
+    }
+
+    #[test]
+    fn test_parse_pressure() {
+        let psi = "some avg10=1.20 avg60=0.80 avg300=0.30 total=123456\n\
+                   full avg10=0.50 avg60=0.10 avg300=0.00 total=4567\n";
+        let p = parse_pressure("memory", psi).unwrap();
+        assert_eq!((p.some, p.full), (1.2, Some(0.5)));
+        assert_eq!(parse_pressure("cpu", "").map(|p| p.some), None);
     }
 
     #[test]
-    fn test_get_resource_context() {
-        let _ = get_resource_context(std::process::id());
+    fn test_parse_inhibitor() {
+        let state = "# This is private data. Do not parse.\nWHAT=sleep:idle\nMODE=delay\n\
+                     UID=1000\nPID=5555\nWHO=Chrome\nWHY=Playing Audio\n";
+        assert_eq!(
+            parse_inhibitor(state, 5555),
+            Some((false, "sleep, idle (delay): Playing Audio".to_string()))
+        );
+        assert_eq!(parse_inhibitor(state, 1234), None);
+
+        let blocking = "WHAT=idle:sleep\nMODE=block\nPID=42\n";
+        assert_eq!(
+            parse_inhibitor(blocking, 42),
+            Some((true, "idle, sleep (block)".to_string()))
+        );
     }
 }
@@ -1,20 +1,93 @@
-use super::net::parse_ip_port;
+use super::net::{get_socket_table, parse_ip_port};
+use super::netlink;
+use crate::adapters::proc::ProcFs;
 use crate::core::models::SocketInfo;
 use std::collections::HashMap;
 use std::fs;
 
-pub fn get_socket_state(_pid: u32) -> HashMap<u64, SocketInfo> {
-    let mut states = HashMap::new();
-    if let Ok(content) = fs::read_to_string("/proc/net/tcp") {
-        states.extend(parse_tcp_states(&content));
+const INET_TABLES: &[(&str, &str)] = &[
+    ("net/tcp", "tcp"),
+    ("net/tcp6", "tcp"),
+    ("net/udp", "udp"),
+    ("net/udp6", "udp"),
+];
+const RAW_TABLES: &[(&str, &str)] = &[("net/raw", "raw"), ("net/raw6", "raw")];
+
+// Set in the flags column of /proc/net/unix for listening sockets.
+const UNIX_ACCEPTCON: u32 = 1 << 16;
+
+pub fn get_socket_state(pid: u32) -> HashMap<u64, SocketInfo> {
+    get_pid_socket_states(&ProcFs::default(), pid)
+}
+
+/// The sockets `pid` holds open, keyed by inode, looked up in the tables of
+/// the network namespace it lives in.
+pub fn get_pid_socket_states(procfs: &ProcFs, pid: u32) -> HashMap<u64, SocketInfo> {
+    let mut table = get_socket_table(procfs, &[pid]);
+    let held = table.pid_inodes.remove(&pid).unwrap_or_default();
+    table.sockets.retain(|inode, _| held.contains(inode));
+    table.sockets
+}
+
+/// Prefers a netlink dump and falls back to the `/proc/net` text tables.
+/// Netlink only sees the caller's own network namespace, so it stands in
+/// for the live `/proc` only; raw sockets always come from procfs.
+pub fn get_all_socket_states(procfs: &ProcFs) -> HashMap<u64, SocketInfo> {
+    let dumped = if *procfs == ProcFs::default() {
+        netlink::dump_sockets().ok()
+    } else {
+        None
+    };
+    let mut states = dumped.unwrap_or_else(|| read_procfs_states(procfs));
+    states.extend(read_inet_tables(procfs, RAW_TABLES));
+    states
+}
+
+pub fn read_procfs_states(procfs: &ProcFs) -> HashMap<u64, SocketInfo> {
+    let mut states = read_inet_tables(procfs, INET_TABLES);
+    if let Ok(content) = fs::read_to_string(procfs.path("net/unix")) {
+        states.extend(parse_unix_states(&content));
     }
-    if let Ok(content) = fs::read_to_string("/proc/net/tcp6") {
-        states.extend(parse_tcp_states(&content));
+    states
+}
+
+fn read_inet_tables(procfs: &ProcFs, tables: &[(&str, &str)]) -> HashMap<u64, SocketInfo> {
+    let mut states = HashMap::new();
+    for (table, protocol) in tables {
+        if let Ok(content) = fs::read_to_string(procfs.path(table)) {
+            states.extend(parse_inet_states(&content, protocol));
+        }
     }
     states
 }
 
-fn parse_tcp_states(content: &str) -> HashMap<u64, SocketInfo> {
+pub fn tcp_state_name(hex: &str) -> &'static str {
+    match hex {
+        "01" => "ESTABLISHED",
+        "02" => "SYN_SENT",
+        "03" => "SYN_RECV",
+        "04" => "FIN_WAIT1",
+        "05" => "FIN_WAIT2",
+        "06" => "TIME_WAIT",
+        "07" => "CLOSE",
+        "08" => "CLOSE_WAIT",
+        "09" => "LAST_ACK",
+        "0A" => "LISTEN",
+        "0B" => "CLOSING",
+        _ => "UNKNOWN",
+    }
+}
+
+// UDP and raw sockets reuse the TCP state codes, but an unconnected socket
+// reports CLOSE; `ss` calls that UNCONN, which reads better.
+pub(super) fn state_name(hex: &str, protocol: &str) -> &'static str {
+    match (protocol, tcp_state_name(hex)) {
+        ("udp" | "raw", "CLOSE") => "UNCONN",
+        (_, name) => name,
+    }
+}
+
+fn parse_inet_states(content: &str, protocol: &str) -> HashMap<u64, SocketInfo> {
     let mut states = HashMap::new();
     for line in content.lines().skip(1) {
         let parts: Vec<&str> = line.split_whitespace().collect();
@@ -22,22 +95,7 @@ fn parse_tcp_states(content: &str) -> HashMap<u64, SocketInfo> {
             if let Ok(inode) = parts[9].parse::<u64>() {
                 let local_hex = parts[1];
                 let remote_hex = parts[2];
-                let state_hex = parts[3];
-
-                let state_str = match state_hex {
-                    "01" => "ESTABLISHED",
-                    "02" => "SYN_SENT",
-                    "03" => "SYN_RECV",
-                    "04" => "FIN_WAIT1",
-                    "05" => "FIN_WAIT2",
-                    "06" => "TIME_WAIT",
-                    "07" => "CLOSE",
-                    "08" => "CLOSE_WAIT",
-                    "09" => "LAST_ACK",
-                    "0A" => "LISTEN",
-                    "0B" => "CLOSING",
-                    _ => "UNKNOWN",
-                };
+                let state_str = state_name(parts[3], protocol);
 
                 let mut local = "0.0.0.0:0".to_string();
                 let mut remote = "0.0.0.0:0".to_string();
@@ -45,14 +103,23 @@ fn parse_tcp_states(content: &str) -> HashMap<u64, SocketInfo> {
 
                 if let Some((ip, p)) = parse_ip_port(local_hex) {
                     local = format!("{}:{}", ip, p);
-                    port = p;
+                    // For raw sockets this column holds the IP protocol number.
+                    if protocol != "raw" {
+                        port = p;
+                    }
                 }
 
                 if let Some((ip, p)) = parse_ip_port(remote_hex) {
                     remote = format!("{}:{}", ip, p);
                 }
 
-                let info = SocketInfo::new(port, state_str.to_string(), local, remote);
+                let mut info = SocketInfo::new(port, state_str.to_string(), local, remote)
+                    .with_protocol(protocol);
+                if let Some((tx, rx)) = parts[4].split_once(':') {
+                    info.tx_queue = u32::from_str_radix(tx, 16).unwrap_or(0);
+                    info.rx_queue = u32::from_str_radix(rx, 16).unwrap_or(0);
+                }
+                info.uid = parts[7].parse().ok();
 
                 states.insert(inode, info);
             }
@@ -61,6 +128,33 @@ fn parse_tcp_states(content: &str) -> HashMap<u64, SocketInfo> {
     states
 }
 
+fn parse_unix_states(content: &str) -> HashMap<u64, SocketInfo> {
+    let mut states = HashMap::new();
+    for line in content.lines().skip(1) {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() < 7 {
+            continue;
+        }
+        let (Ok(flags), Ok(inode)) = (u32::from_str_radix(parts[3], 16), parts[6].parse::<u64>())
+        else {
+            continue;
+        };
+        let state = if flags & UNIX_ACCEPTCON != 0 {
+            "LISTEN"
+        } else {
+            match parts[5] {
+                "03" => "CONNECTED",
+                "02" => "CONNECTING",
+                "04" => "DISCONNECTING",
+                _ => "UNCONN",
+            }
+        };
+        let path = parts.get(7).map(|p| p.to_string());
+        states.insert(inode, SocketInfo::unix(path, state.to_string()));
+    }
+    states
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -71,7 +165,7 @@ mod tests {
    0: 00000000:1F90 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 21623 1 0000000000000000 100 0 0 10 0
    1: 0100007F:0277 0100007F:1F90 01 00000000:00000000 00:00000000 00000000  1000        0 24159 1 0000000000000000 100 0 0 10 0
 ";
-        let states = parse_tcp_states(content);
+        let states = parse_inet_states(content, "tcp");
 
         // Inode 21623, State 0A -> LISTEN
         if let Some(s) = states.get(&21623) {
@@ -85,4 +179,48 @@ mod tests {
             assert_eq!(s.port, 631); // 0277 hex -> 631
         }
     }
+
+    #[test]
+    fn test_parse_udp_and_raw_states() {
+        let udp = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode ref pointer drops
+  1: 3500007F:0035 00000000:0000 07 00000000:00000000 00:00000000 00000000   101        0 31001 2 0000000000000000 0
+";
+        let s = &parse_inet_states(udp, "udp")[&31001];
+        assert_eq!(s.protocol, "udp");
+        assert_eq!(s.state, "UNCONN");
+        assert_eq!(s.port, 53);
+        assert_eq!(s.local_addr, "127.0.0.53:53");
+        assert_eq!(s.uid, Some(101));
+
+        let raw = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode ref pointer drops
+  1: 00000000:0001 00000000:0000 07 00000000:00000000 00:00000000 00000000     0        0 31002 2 0000000000000000 0
+";
+        let s = &parse_inet_states(raw, "raw")[&31002];
+        assert_eq!(s.protocol, "raw");
+        assert_eq!(s.port, 0);
+    }
+
+    #[test]
+    fn test_parse_unix_states() {
+        let content = "Num       RefCount Protocol Flags    Type St Inode Path
+0000000000000000: 00000002 00000000 00010000 0001 01 41001 /run/docker.sock
+0000000000000000: 00000003 00000000 00000000 0001 03 41002 /run/docker.sock
+0000000000000000: 00000003 00000000 00000000 0001 03 41003
+0000000000000000: 00000002 00000000 00010000 0001 01 41004 @/tmp/.X11-unix/X0
+";
+        let states = parse_unix_states(content);
+        assert_eq!(states.len(), 4);
+
+        let listener = &states[&41001];
+        assert_eq!(listener.protocol, "unix");
+        assert_eq!(listener.state, "LISTEN");
+        assert_eq!(listener.unix_path.as_deref(), Some("/run/docker.sock"));
+
+        assert_eq!(states[&41002].state, "CONNECTED");
+        assert_eq!(states[&41003].unix_path, None);
+        assert_eq!(
+            states[&41004].unix_path.as_deref(),
+            Some("@/tmp/.X11-unix/X0")
+        );
+    }
 }
@@ -0,0 +1,83 @@
+use crate::adapters::proc::ProcFs;
+use std::fs;
+
+// USER_HZ is fixed at 100 on every architecture Linux exposes to userspace.
+const CLOCK_TICKS: u64 = 100;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcStat {
+    pub comm: String,
+    pub state: char,
+    pub ppid: u32,
+    pub start_ticks: u64,
+}
+
+impl ProcStat {
+    /// Start time in seconds since the epoch, given the boot time.
+    pub fn start_time(&self, boot_time: u64) -> u64 {
+        boot_time + self.start_ticks / CLOCK_TICKS
+    }
+}
+
+pub fn read_stat(procfs: &ProcFs, pid: u32) -> Option<ProcStat> {
+    let content = fs::read_to_string(procfs.pid_path(pid, "stat")).ok()?;
+    parse_stat(&content)
+}
+
+pub fn read_status(procfs: &ProcFs, pid: u32) -> Option<String> {
+    fs::read_to_string(procfs.pid_path(pid, "status")).ok()
+}
+
+fn parse_stat(content: &str) -> Option<ProcStat> {
+    // comm is wrapped in parentheses and may itself contain spaces or ')'.
+    let open = content.find('(')?;
+    let close = content.rfind(')')?;
+    let comm = content.get(open + 1..close)?.to_string();
+    let fields: Vec<&str> = content.get(close + 1..)?.split_whitespace().collect();
+
+    Some(ProcStat {
+        comm,
+        state: fields.first()?.chars().next()?,
+        ppid: fields.get(1)?.parse().ok()?,
+        start_ticks: fields.get(19)?.parse().ok()?,
+    })
+}
+
+/// First value of a `Key:` line in `/proc/<pid>/status`.
+pub fn status_field<'a>(status: &'a str, key: &str) -> Option<&'a str> {
+    status.lines().find_map(|line| {
+        let (k, v) = line.split_once(':')?;
+        if k == key {
+            v.split_whitespace().next()
+        } else {
+            None
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_stat() {
+        let content = "1234 (tmux: server) S 1 1234 1234 0 -1 4194560 1337 0 0 0 \
+                       12 7 0 0 20 0 1 0 250000 12345678 900 18446744073709551615";
+        let stat = parse_stat(content).unwrap();
+        assert_eq!(stat.comm, "tmux: server");
+        assert_eq!(stat.state, 'S');
+        assert_eq!(stat.ppid, 1);
+        assert_eq!(stat.start_ticks, 250000);
+        assert_eq!(stat.start_time(1_700_000_000), 1_700_002_500);
+
+        assert!(parse_stat("garbage").is_none());
+    }
+
+    #[test]
+    fn test_status_field() {
+        let status = "Name:\tnginx\nUid:\t33\t33\t33\t33\nVmRSS:\t   5120 kB\n";
+        assert_eq!(status_field(status, "Uid"), Some("33"));
+        assert_eq!(status_field(status, "VmRSS"), Some("5120"));
+        assert_eq!(status_field(status, "Gid"), None);
+    }
+}
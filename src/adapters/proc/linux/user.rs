@@ -1,12 +1,31 @@
+use crate::adapters::hostfs::HostFs;
+use std::collections::HashMap;
 use std::fs;
 
-pub fn get_username(uid: &str) -> Option<String> {
-    if let Ok(passwd) = fs::read_to_string("/etc/passwd") {
+pub fn get_username(host: &HostFs, uid: &str) -> Option<String> {
+    if let Ok(passwd) = fs::read_to_string(host.path("/etc/passwd")) {
         return parse_passwd(&passwd, uid);
     }
     None
 }
 
+/// Every uid → username mapping in the host's `/etc/passwd`.
+pub fn get_users(host: &HostFs) -> HashMap<String, String> {
+    fs::read_to_string(host.path("/etc/passwd"))
+        .map(|passwd| parse_users(&passwd))
+        .unwrap_or_default()
+}
+
+fn parse_users(content: &str) -> HashMap<String, String> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.split(':').collect();
+            (parts.len() >= 3).then(|| (parts[2].to_string(), parts[0].to_string()))
+        })
+        .collect()
+}
+
 fn parse_passwd(content: &str, target_uid: &str) -> Option<String> {
     for line in content.lines() {
         let parts: Vec<&str> = line.split(':').collect();
@@ -30,5 +49,9 @@ user:x:1000:1000:User Name,,,:/home/user:/bin/bash";
         assert_eq!(parse_passwd(content, "0"), Some("root".to_string()));
         assert_eq!(parse_passwd(content, "1000"), Some("user".to_string()));
         assert_eq!(parse_passwd(content, "9999"), None);
+
+        let users = parse_users(content);
+        assert_eq!(users.len(), 3);
+        assert_eq!(users.get("1").map(String::as_str), Some("daemon"));
     }
 }
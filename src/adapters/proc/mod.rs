@@ -2,6 +2,11 @@
 pub mod darwin;
 #[cfg(target_os = "linux")]
 pub mod linux;
+pub mod procfs;
+pub mod table;
+
+pub use procfs::ProcFs;
+pub use table::SocketTable;
 
 // Re-export platform-specific implementations
 #[cfg(target_os = "macos")]
@@ -14,7 +19,7 @@ pub use darwin::socketstate::get_socket_state;
 #[cfg(target_os = "linux")]
 pub use crate::core::models::SocketInfo;
 #[cfg(target_os = "linux")]
-pub use linux::net::{get_listening_sockets, get_sockets_for_pid};
+pub use linux::net::{get_listening_sockets, get_socket_table, get_sockets_for_pid};
 #[cfg(target_os = "linux")]
 pub use linux::socketstate::get_socket_state;
 
@@ -27,6 +32,9 @@ pub use windows::net::{get_listening_sockets, get_sockets_for_pid};
 #[cfg(target_os = "windows")]
 pub use windows::socketstate::get_socket_state;
 
+#[cfg(not(target_os = "linux"))]
+pub use table::get_socket_table;
+
 // For non-mac/linux/windows platforms, provide stubs
 #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
 pub mod stubs {
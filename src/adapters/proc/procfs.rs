@@ -0,0 +1,75 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Root of a procfs tree: the live `/proc`, or a directory laid out the
+/// same way such as a captured fixture.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcFs {
+    root: PathBuf,
+}
+
+impl Default for ProcFs {
+    fn default() -> Self {
+        Self::new("/proc")
+    }
+}
+
+impl ProcFs {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Path of a system-wide entry such as `net/tcp` or `stat`.
+    pub fn path(&self, rel: &str) -> PathBuf {
+        self.root.join(rel)
+    }
+
+    /// Path of a per-process entry such as `cmdline` or `fd`.
+    pub fn pid_path(&self, pid: u32, rel: &str) -> PathBuf {
+        self.root.join(pid.to_string()).join(rel)
+    }
+
+    /// The tree under `/proc/<pid>`, whose `net/` tables describe the
+    /// network namespace that process lives in.
+    pub fn pid_root(&self, pid: u32) -> ProcFs {
+        ProcFs::new(self.root.join(pid.to_string()))
+    }
+
+    /// Numeric directories under the root, sorted.
+    pub fn pids(&self) -> Vec<u32> {
+        let mut pids: Vec<u32> = fs::read_dir(&self.root)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .filter_map(|e| e.file_name().to_str()?.parse().ok())
+                    .collect()
+            })
+            .unwrap_or_default();
+        pids.sort();
+        pids
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paths() {
+        let procfs = ProcFs::new("/host/proc");
+        assert_eq!(procfs.path("net/tcp"), PathBuf::from("/host/proc/net/tcp"));
+        assert_eq!(
+            procfs.pid_path(42, "cgroup"),
+            PathBuf::from("/host/proc/42/cgroup")
+        );
+        assert_eq!(
+            procfs.pid_root(42).path("net/tcp"),
+            PathBuf::from("/host/proc/42/net/tcp")
+        );
+        assert_eq!(ProcFs::default().root(), Path::new("/proc"));
+    }
+}
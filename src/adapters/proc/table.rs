@@ -0,0 +1,90 @@
+use crate::core::models::SocketInfo;
+use std::collections::HashMap;
+
+/// Sockets keyed by inode, plus the inodes each PID holds open.
+#[derive(Debug, Clone, Default)]
+pub struct SocketTable {
+    pub sockets: HashMap<u64, SocketInfo>,
+    pub pid_inodes: HashMap<u32, Vec<u64>>,
+}
+
+impl SocketTable {
+    pub fn sockets_for_pid(&self, pid: u32) -> Vec<&SocketInfo> {
+        self.pid_inodes
+            .get(&pid)
+            .map(|inodes| {
+                inodes
+                    .iter()
+                    .filter_map(|inode| self.sockets.get(inode))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn pids_for_port(&self, port: u16) -> Vec<u32> {
+        self.pids_with(|s| s.is_inet() && s.port == port)
+    }
+
+    /// PIDs holding at least one socket that satisfies `matches`, sorted.
+    pub fn pids_with(&self, matches: impl Fn(&SocketInfo) -> bool) -> Vec<u32> {
+        let mut pids: Vec<u32> = self
+            .pid_inodes
+            .iter()
+            .filter(|(_, inodes)| {
+                inodes
+                    .iter()
+                    .any(|inode| self.sockets.get(inode).is_some_and(&matches))
+            })
+            .map(|(pid, _)| *pid)
+            .collect();
+        pids.sort();
+        pids
+    }
+}
+
+// Without a global inode table, fall back to the per-PID socket query and
+// hand out synthetic keys so the index still works the same way.
+#[cfg(not(target_os = "linux"))]
+pub fn get_socket_table(_procfs: &super::ProcFs, pids: &[u32]) -> SocketTable {
+    let mut table = SocketTable::default();
+    for &pid in pids {
+        for (_, info) in super::get_socket_state(pid) {
+            let key = table.sockets.len() as u64;
+            table.sockets.insert(key, info);
+            table.pid_inodes.entry(pid).or_default().push(key);
+        }
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn socket(port: u16) -> SocketInfo {
+        SocketInfo::new(
+            port,
+            "LISTEN".to_string(),
+            "0.0.0.0".to_string(),
+            String::new(),
+        )
+    }
+
+    #[test]
+    fn test_socket_table_lookup() {
+        let mut table = SocketTable::default();
+        table.sockets.insert(10, socket(80));
+        table.sockets.insert(11, socket(443));
+        table.sockets.insert(12, socket(80));
+        table.pid_inodes.insert(100, vec![10, 11]);
+        table.pid_inodes.insert(200, vec![12]);
+        table.pid_inodes.insert(300, vec![99]);
+
+        assert_eq!(table.sockets_for_pid(100).len(), 2);
+        assert!(table.sockets_for_pid(300).is_empty());
+        assert_eq!(table.pids_for_port(80), vec![100, 200]);
+        assert_eq!(table.pids_for_port(443), vec![100]);
+        assert!(table.pids_for_port(22).is_empty());
+        assert_eq!(table.pids_with(|s| s.port == 443), vec![100]);
+    }
+}
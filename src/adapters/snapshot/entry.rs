@@ -0,0 +1,71 @@
+#[cfg(target_os = "linux")]
+use crate::adapters::proc::{self as network, ProcFs};
+use sysinfo::ProcessStatus;
+
+/// Raw per-process data captured from the process table.
+#[derive(Debug, Clone)]
+pub struct ProcEntry {
+    pub pid: u32,
+    pub parent_pid: Option<u32>,
+    pub name: String,
+    pub cmd: Vec<String>,
+    pub exe_path: Option<String>,
+    pub uid: Option<String>,
+    pub start_time: u64,
+    pub cwd: Option<String>,
+    pub env: Vec<String>,
+    pub status: ProcessStatus,
+    pub cpu_usage: f32,
+    pub memory: u64,
+}
+
+pub(super) fn entry_from_sysinfo(pid: u32, process: &sysinfo::Process) -> ProcEntry {
+    ProcEntry {
+        pid,
+        parent_pid: process.parent().map(|p| p.as_u32()),
+        name: process.name().to_string_lossy().to_string(),
+        cmd: process
+            .cmd()
+            .iter()
+            .map(|s| s.to_string_lossy().to_string())
+            .collect(),
+        exe_path: process.exe().map(|p| p.display().to_string()),
+        uid: process.user_id().map(|u| u.to_string()),
+        start_time: process.start_time(),
+        cwd: process.cwd().map(|p| p.display().to_string()),
+        env: process
+            .environ()
+            .iter()
+            .map(|s| s.to_string_lossy().to_string())
+            .collect(),
+        status: process.status(),
+        cpu_usage: process.cpu_usage(),
+        memory: process.memory(),
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub(super) fn entry_from_procfs(procfs: &ProcFs, pid: u32, boot_time: u64) -> Option<ProcEntry> {
+    use network::linux::{cmdline, process, stat};
+
+    let proc_stat = stat::read_stat(procfs, pid)?;
+    let status = stat::read_status(procfs, pid).unwrap_or_default();
+    let memory_kb: u64 = stat::status_field(&status, "VmRSS")
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(0);
+
+    Some(ProcEntry {
+        pid,
+        parent_pid: Some(proc_stat.ppid).filter(|&ppid| ppid != 0),
+        name: proc_stat.comm.clone(),
+        cmd: cmdline::get_cmdline(procfs, pid),
+        exe_path: process::get_process_exe(procfs, pid),
+        uid: stat::status_field(&status, "Uid").map(str::to_string),
+        start_time: proc_stat.start_time(boot_time),
+        cwd: process::get_process_cwd(procfs, pid),
+        env: cmdline::get_environ(procfs, pid),
+        status: ProcessStatus::from(proc_stat.state),
+        cpu_usage: 0.0,
+        memory: memory_kb * 1024,
+    })
+}
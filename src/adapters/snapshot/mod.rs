@@ -0,0 +1,297 @@
+use crate::adapters::hostfs::HostFs;
+use crate::adapters::proc::{self as network, ProcFs, SocketTable};
+use crate::adapters::source;
+use crate::core::models::{
+    CgroupInfo, ContainerInfo, FileContext, HeldPath, MemoryContext, ResourceContext, SocketInfo,
+};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use sysinfo::System;
+
+mod entry;
+
+#[cfg(target_os = "linux")]
+use entry::entry_from_procfs;
+use entry::entry_from_sysinfo;
+pub use entry::ProcEntry;
+
+/// A single consistent scan of the system: the process table, the socket
+/// tables, each process's cgroup and the user database, indexed for
+/// lookups. Everything but the process table is read in one pass the first
+/// time it is needed.
+#[derive(Debug, Default)]
+pub struct SystemSnapshot {
+    procfs: ProcFs,
+    host: HostFs,
+    processes: HashMap<u32, ProcEntry>,
+    sockets: OnceLock<SocketTable>,
+    cgroups: OnceLock<HashMap<u32, String>>,
+    users: OnceLock<HashMap<String, String>>,
+}
+
+impl SystemSnapshot {
+    pub fn capture(sys: &System) -> Self {
+        let processes: HashMap<u32, ProcEntry> = sys
+            .processes()
+            .iter()
+            .filter(|(_, p)| p.thread_kind().is_none())
+            .map(|(pid, p)| (pid.as_u32(), entry_from_sysinfo(pid.as_u32(), p)))
+            .collect();
+
+        Self {
+            processes,
+            ..Default::default()
+        }
+    }
+
+    /// Builds the process table by parsing a procfs tree directly, so a
+    /// captured fixture can be read the same way as the live `/proc`.
+    #[cfg(target_os = "linux")]
+    pub fn from_procfs(procfs: ProcFs) -> Self {
+        let boot_time = network::linux::boot::get_boot_time(&procfs);
+        let processes = procfs
+            .pids()
+            .into_iter()
+            .filter_map(|pid| entry_from_procfs(&procfs, pid, boot_time))
+            .map(|e| (e.pid, e))
+            .collect();
+
+        Self {
+            procfs,
+            processes,
+            ..Default::default()
+        }
+    }
+
+    /// Reads host files such as `/etc/passwd` under `host` instead of `/`.
+    pub fn with_host(mut self, host: HostFs) -> Self {
+        self.host = host;
+        self
+    }
+
+    pub fn host(&self) -> &HostFs {
+        &self.host
+    }
+
+    pub fn from_parts(
+        entries: Vec<ProcEntry>,
+        sockets: SocketTable,
+        cgroups: HashMap<u32, String>,
+    ) -> Self {
+        Self {
+            processes: entries.into_iter().map(|e| (e.pid, e)).collect(),
+            sockets: OnceLock::from(sockets),
+            cgroups: OnceLock::from(cgroups),
+            ..Default::default()
+        }
+    }
+
+    fn sockets(&self) -> &SocketTable {
+        self.sockets
+            .get_or_init(|| network::get_socket_table(&self.procfs, &self.pids()))
+    }
+
+    fn cgroups(&self) -> &HashMap<u32, String> {
+        self.cgroups.get_or_init(|| {
+            self.processes
+                .keys()
+                .filter_map(|&pid| source::read_cgroup(&self.procfs, pid).map(|c| (pid, c)))
+                .collect()
+        })
+    }
+
+    pub fn process(&self, pid: u32) -> Option<&ProcEntry> {
+        self.processes.get(&pid)
+    }
+
+    pub fn pids(&self) -> Vec<u32> {
+        let mut pids: Vec<u32> = self.processes.keys().copied().collect();
+        pids.sort();
+        pids
+    }
+
+    pub fn find_by_name(&self, query: &str) -> Vec<&ProcEntry> {
+        let query = query.to_lowercase();
+        let mut matches: Vec<&ProcEntry> = self
+            .processes
+            .values()
+            .filter(|p| p.name.to_lowercase().contains(&query))
+            .collect();
+        matches.sort_by_key(|p| p.pid);
+        matches
+    }
+
+    pub fn find_by_port(&self, port: u16) -> Vec<&ProcEntry> {
+        self.find_by_socket(|s| s.is_inet() && s.port == port)
+    }
+
+    /// Processes holding at least one socket that satisfies `matches`.
+    pub fn find_by_socket(&self, matches: impl Fn(&SocketInfo) -> bool) -> Vec<&ProcEntry> {
+        self.sockets()
+            .pids_with(matches)
+            .into_iter()
+            .filter_map(|pid| self.processes.get(&pid))
+            .collect()
+    }
+
+    pub fn sockets_for_pid(&self, pid: u32) -> Vec<&SocketInfo> {
+        self.sockets().sockets_for_pid(pid)
+    }
+
+    pub fn cgroup(&self, pid: u32) -> Option<&str> {
+        self.cgroups().get(&pid).map(|s| s.as_str())
+    }
+
+    pub fn file_context(&self, pid: u32) -> Option<FileContext> {
+        #[cfg(target_os = "linux")]
+        return network::linux::filecontext::get_file_context(&self.procfs, pid);
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = pid;
+            None
+        }
+    }
+
+    pub fn memory_context(&self, pid: u32) -> Option<MemoryContext> {
+        #[cfg(target_os = "linux")]
+        return network::linux::maps::get_memory_context(&self.procfs, pid);
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = pid;
+            None
+        }
+    }
+
+    /// The container `pid` runs in, named from its runtime's state files.
+    pub fn container_info(&self, pid: u32) -> Option<ContainerInfo> {
+        source::get_container_info(&self.host, self.cgroup(pid)?)
+    }
+
+    pub fn cgroup_info(&self, pid: u32) -> Option<CgroupInfo> {
+        #[cfg(target_os = "linux")]
+        return network::linux::cgroup::get_cgroup_info(&self.host, self.cgroup(pid)?);
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = pid;
+            None
+        }
+    }
+
+    pub fn resource_context(&self, pid: u32) -> Option<ResourceContext> {
+        #[cfg(target_os = "linux")]
+        return network::linux::resource::get_resource_context(
+            &self.procfs,
+            &self.host,
+            pid,
+            self.cgroup(pid),
+        );
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = pid;
+            None
+        }
+    }
+
+    pub fn held_paths(&self, pid: u32) -> Vec<HeldPath> {
+        #[cfg(target_os = "linux")]
+        return network::linux::filecontext::get_held_paths(&self.procfs, pid);
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = pid;
+            Vec::new()
+        }
+    }
+
+    pub fn username(&self, uid: &str) -> Option<&str> {
+        #[cfg(target_os = "linux")]
+        {
+            self.users
+                .get_or_init(|| network::linux::user::get_users(&self.host))
+                .get(uid)
+                .map(|s| s.as_str())
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = (uid, &self.host, &self.users);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sysinfo::ProcessStatus;
+
+    fn entry(pid: u32, name: &str) -> ProcEntry {
+        ProcEntry {
+            pid,
+            parent_pid: Some(1),
+            name: name.to_string(),
+            cmd: vec![name.to_string()],
+            exe_path: None,
+            uid: None,
+            start_time: 0,
+            cwd: None,
+            env: vec![],
+            status: ProcessStatus::Run,
+            cpu_usage: 0.0,
+            memory: 0,
+        }
+    }
+
+    fn snapshot() -> SystemSnapshot {
+        let mut sockets = SocketTable::default();
+        sockets.sockets.insert(
+            7,
+            SocketInfo::new(
+                8080,
+                "LISTEN".to_string(),
+                "0.0.0.0:8080".to_string(),
+                String::new(),
+            ),
+        );
+        sockets.pid_inodes.insert(20, vec![7]);
+
+        let mut cgroups = HashMap::new();
+        cgroups.insert(20, "0::/system.slice/web.service".to_string());
+
+        SystemSnapshot::from_parts(
+            vec![entry(1, "init"), entry(10, "Nginx"), entry(20, "nginx")],
+            sockets,
+            cgroups,
+        )
+    }
+
+    #[test]
+    fn test_find_by_name_is_case_insensitive() {
+        let snap = snapshot();
+        let pids: Vec<u32> = snap.find_by_name("NGINX").iter().map(|p| p.pid).collect();
+        assert_eq!(pids, vec![10, 20]);
+    }
+
+    #[test]
+    fn test_find_by_port_uses_inode_index() {
+        let snap = snapshot();
+        let owners = snap.find_by_port(8080);
+        assert_eq!(owners.len(), 1);
+        assert_eq!(owners[0].pid, 20);
+        assert!(snap.find_by_port(9090).is_empty());
+        assert_eq!(snap.sockets_for_pid(20).len(), 1);
+        assert!(snap.sockets_for_pid(10).is_empty());
+    }
+
+    #[test]
+    fn test_cgroup_lookup() {
+        let snap = snapshot();
+        assert_eq!(snap.cgroup(20), Some("0::/system.slice/web.service"));
+        assert_eq!(snap.cgroup(1), None);
+        assert_eq!(snap.pids(), vec![1, 10, 20]);
+    }
+}
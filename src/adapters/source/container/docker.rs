@@ -0,0 +1,62 @@
+use crate::core::models::{ContainerExit, ContainerState};
+use chrono::DateTime;
+use serde_json::Value;
+
+/// Lifecycle state from an inspect response, or from the engine's own
+/// `config.v2.json`, which shares its layout.
+pub fn parse_state(config: &Value) -> ContainerState {
+    let state = &config["State"];
+    let label = |key: &str| config["Config"]["Labels"][key].as_str().map(str::to_string);
+    let status = match state["Status"].as_str() {
+        Some(status) => status.to_string(),
+        // config.v2.json keeps flags rather than a status.
+        None if state["Restarting"] == true => "restarting".to_string(),
+        None if state["Paused"] == true => "paused".to_string(),
+        None if state["Running"] == true => "running".to_string(),
+        None => "exited".to_string(),
+    };
+    // Starting a container clears its exit code, so only a stopped one
+    // still describes how its last run ended.
+    let finished_at = timestamp(&state["FinishedAt"]).filter(|_| status != "running");
+    let last_exit = finished_at.map(|finished_at| ContainerExit {
+        code: state["ExitCode"].as_i64().unwrap_or_default(),
+        finished_at,
+        oom_killed: state["OOMKilled"] == true,
+    });
+    ContainerState {
+        status,
+        restart_count: config["RestartCount"].as_u64().unwrap_or_default() as u32,
+        started_at: timestamp(&state["StartedAt"]),
+        health: state["Health"]["Status"].as_str().map(str::to_string),
+        failing_streak: state["Health"]["FailingStreak"]
+            .as_u64()
+            .unwrap_or_default() as u32,
+        last_exit,
+        compose_project: label("com.docker.compose.project"),
+        compose_service: label("com.docker.compose.service"),
+    }
+}
+
+// Docker writes Go's zero time, `0001-01-01T00:00:00Z`, for events that
+// have not happened.
+fn timestamp(value: &Value) -> Option<u64> {
+    let time = DateTime::parse_from_rfc3339(value.as_str()?).ok()?;
+    u64::try_from(time.timestamp()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_state_from_config_file() {
+        let config: Value = serde_json::from_str(
+            r#"{"State":{"Running":true,"StartedAt":"2024-05-02T09:14:28Z","FinishedAt":"0001-01-01T00:00:00Z"}}"#,
+        )
+        .unwrap();
+        let state = parse_state(&config);
+        assert_eq!(state.status, "running");
+        assert_eq!(state.last_exit, None);
+        assert_eq!(state.compose_project, None);
+    }
+}
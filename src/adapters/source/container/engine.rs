@@ -0,0 +1,81 @@
+use crate::adapters::source::unixhttp;
+use serde_json::Value;
+use std::io;
+use std::path::Path;
+
+/// `GET /containers/<id>/json` from the Docker Engine API, spoken as plain
+/// HTTP over the engine's unix socket.
+pub fn inspect(socket: &Path, id: &str) -> io::Result<Value> {
+    let (status, body) =
+        unixhttp::request(socket, "GET", &format!("/containers/{}/json", id), None)?;
+    if status != 200 {
+        return Err(io::Error::other(format!("docker API returned {}", status)));
+    }
+    serde_json::from_slice(&body).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::source::container::docker::parse_state;
+    use crate::adapters::testutil::TempTree;
+    use crate::core::models::ContainerExit;
+    use std::io::{Read, Write};
+    use std::os::unix::net::UnixListener;
+    use std::thread;
+
+    // Answers a single request with `response`, as the engine would.
+    fn serve(name: &str, response: String) -> (TempTree, std::path::PathBuf) {
+        let tree = TempTree::new(name);
+        let socket = tree.path("api.sock");
+        let listener = UnixListener::bind(&socket).unwrap();
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = [0u8; 1024];
+            let n = stream.read(&mut request).unwrap();
+            assert!(request[..n].starts_with(b"GET /containers/4f3c/json HTTP/1.1\r\n"));
+            stream.write_all(response.as_bytes()).unwrap();
+        });
+        (tree, socket)
+    }
+
+    #[test]
+    fn test_inspect_over_socket() {
+        let body = r#"{"RestartCount":3,"State":{"Status":"exited","StartedAt":"2024-05-02T09:14:28.5Z","FinishedAt":"2024-05-02T09:14:20Z","ExitCode":137,"OOMKilled":true,"Health":{"Status":"unhealthy","FailingStreak":4}},"Config":{"Labels":{"com.docker.compose.project":"web","com.docker.compose.service":"api"}}}"#;
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n{:x}\r\n{}\r\n0\r\n\r\n",
+            body.len(),
+            body
+        );
+        let (_tree, socket) = serve("docker", response);
+        let config = inspect(&socket, "4f3c").unwrap();
+
+        let state = parse_state(&config);
+        assert_eq!((state.status.as_str(), state.restart_count), ("exited", 3));
+        assert_eq!(state.started_at, Some(1714641268));
+        assert_eq!(
+            (state.health.as_deref(), state.failing_streak),
+            (Some("unhealthy"), 4)
+        );
+        assert_eq!(
+            state.last_exit,
+            Some(ContainerExit {
+                code: 137,
+                finished_at: 1714641260,
+                oom_killed: true
+            })
+        );
+        assert_eq!(state.compose_project.as_deref(), Some("web"));
+        assert_eq!(state.compose_service.as_deref(), Some("api"));
+    }
+
+    #[test]
+    fn test_inspect_not_found() {
+        let (_tree, socket) = serve(
+            "docker-missing",
+            "HTTP/1.1 404 Not Found\r\nContent-Length: 2\r\n\r\n{}".to_string(),
+        );
+        let err = inspect(&socket, "4f3c").unwrap_err();
+        assert_eq!(err.to_string(), "docker API returned 404");
+    }
+}
@@ -0,0 +1,78 @@
+mod docker;
+#[cfg(target_os = "linux")]
+mod engine;
+mod path;
+mod pod;
+mod state;
+
+use crate::adapters::hostfs::HostFs;
+use crate::adapters::proc::ProcFs;
+use crate::core::models::ContainerInfo;
+use std::fs;
+
+pub use path::parse_container;
+use state::load_state;
+
+pub fn read_cgroup(procfs: &ProcFs, pid: u32) -> Option<String> {
+    fs::read_to_string(procfs.pid_path(pid, "cgroup")).ok()
+}
+
+pub fn detect_container(procfs: &ProcFs, pid: u32) -> Option<String> {
+    read_cgroup(procfs, pid).and_then(|content| parse_cgroup(&content))
+}
+
+/// The runtime a process's container runs under, e.g. `docker`.
+pub fn parse_cgroup(content: &str) -> Option<String> {
+    parse_container(content).map(|c| c.runtime.to_string())
+}
+
+pub fn is_container_process(procfs: &ProcFs, pid: u32) -> bool {
+    detect_container(procfs, pid).is_some()
+}
+
+pub fn get_container_id(procfs: &ProcFs, pid: u32) -> Option<String> {
+    read_cgroup(procfs, pid).and_then(|content| parse_cgroup_id(&content))
+}
+
+pub fn parse_cgroup_id(content: &str) -> Option<String> {
+    parse_container(content).map(|c| c.id)
+}
+
+/// The container described by `cgroup`, named from its runtime's state
+/// files under `host`.
+pub fn get_container_info(host: &HostFs, cgroup: &str) -> Option<ContainerInfo> {
+    let mut info = parse_container(cgroup)?;
+    load_state(host, &mut info);
+    Some(info)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cgroup() {
+        assert_eq!(
+            parse_cgroup("1:name=systemd:/docker/12345"),
+            Some("docker".to_string())
+        );
+        assert_eq!(
+            parse_cgroup("1:name=systemd:/kubepods/123"),
+            Some("kubernetes".to_string())
+        );
+        assert_eq!(parse_cgroup("1:name=systemd:/user.slice"), None);
+    }
+
+    #[test]
+    fn test_parse_cgroup_id() {
+        assert_eq!(
+            parse_cgroup_id("1:name=systemd:/docker/12345abcdef"),
+            Some("12345abcdef".to_string())
+        );
+        assert_eq!(
+            parse_cgroup_id("11:pids:/docker/c50c18d45123"),
+            Some("c50c18d45123".to_string())
+        );
+        assert_eq!(parse_cgroup_id("1:name=systemd:/user.slice"), None);
+    }
+}
@@ -0,0 +1,151 @@
+use crate::core::models::{ContainerInfo, ContainerRuntime};
+
+/// Identifies the container a process runs in from the contents of
+/// `/proc/<pid>/cgroup`, and the Kubernetes pod around it if there is one.
+pub fn parse_container(content: &str) -> Option<ContainerInfo> {
+    content
+        .lines()
+        .filter_map(|line| line.splitn(3, ':').nth(2))
+        .find_map(parse_path)
+}
+
+fn parse_path(path: &str) -> Option<ContainerInfo> {
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    let in_pod = segments.iter().any(|s| s.starts_with("kubepods"));
+
+    // Nested cgroups such as docker's `<scope>/docker/<id>` repeat the ID;
+    // the innermost match is taken so that systemd units inside an LXC
+    // container still resolve to it.
+    let found = segments.iter().enumerate().rev().find_map(|(i, seg)| {
+        let parent = i.checked_sub(1).map(|j| segments[j]);
+        runtime_segment(seg, parent)
+    });
+    let (runtime, id) = match found {
+        Some(found) => found,
+        // A pod's containers sit in a bare `<id>` cgroup when the kubelet
+        // uses the cgroupfs driver.
+        None if in_pod => (
+            ContainerRuntime::Kubernetes,
+            *segments.last().filter(|s| is_id(s))?,
+        ),
+        None => return None,
+    };
+
+    let mut info = ContainerInfo::new(runtime, id);
+    if in_pod {
+        info.pod_uid = segments.iter().find_map(|s| pod_uid(s));
+        info.qos_class = Some(qos_class(&segments).to_string());
+    }
+    Some(info)
+}
+
+// systemd-driver scopes such as `cri-containerd-<id>.scope` name the
+// runtime; cgroupfs layouts put the bare ID under a runtime directory.
+fn runtime_segment<'a>(seg: &'a str, parent: Option<&str>) -> Option<(ContainerRuntime, &'a str)> {
+    const SCOPES: &[(&str, ContainerRuntime)] = &[
+        ("docker-", ContainerRuntime::Docker),
+        ("cri-containerd-", ContainerRuntime::Containerd),
+        ("crio-", ContainerRuntime::CriO),
+        ("libpod-", ContainerRuntime::Podman),
+    ];
+    let name = seg.strip_suffix(".scope").unwrap_or(seg);
+    let scoped = SCOPES.iter().find_map(|(prefix, runtime)| {
+        let id = name.strip_prefix(prefix).filter(|id| is_id(id))?;
+        Some((*runtime, id))
+    });
+    if scoped.is_some() {
+        return scoped;
+    }
+    if let Some(name) = seg.strip_prefix("lxc.payload.") {
+        return Some((ContainerRuntime::Lxc, name));
+    }
+    match parent {
+        Some("docker") if is_id(seg) => Some((ContainerRuntime::Docker, seg)),
+        Some("libpod_parent") if is_id(seg) => Some((ContainerRuntime::Podman, seg)),
+        Some("lxc" | "lxc.payload") => Some((ContainerRuntime::Lxc, seg)),
+        _ => None,
+    }
+}
+
+// `pod<uid>` under cgroupfs; `kubepods-<qos>-pod<uid>.slice` under systemd,
+// which swaps the UID's dashes for underscores.
+fn pod_uid(seg: &str) -> Option<String> {
+    let name = seg.strip_suffix(".slice").unwrap_or(seg);
+    let uid = match name.rsplit_once("-pod") {
+        Some((_, uid)) => uid,
+        None => name.strip_prefix("pod")?,
+    };
+    let uid = uid.replace('_', "-");
+    (!uid.is_empty() && uid.chars().all(|c| c.is_ascii_hexdigit() || c == '-')).then_some(uid)
+}
+
+// Guaranteed pods sit directly under `kubepods`; the others in a subtree
+// named for their class.
+fn qos_class(segments: &[&str]) -> &'static str {
+    let in_class = |class: &str| {
+        segments
+            .iter()
+            .any(|s| *s == class || s.starts_with(&format!("kubepods-{}", class)))
+    };
+    if in_class("besteffort") {
+        "besteffort"
+    } else if in_class("burstable") {
+        "burstable"
+    } else {
+        "guaranteed"
+    }
+}
+
+fn is_id(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_runtime_scopes() {
+        let docker = parse_container("0::/system.slice/docker-4f3c2a1b9d8e.scope\n").unwrap();
+        assert_eq!(docker.runtime, ContainerRuntime::Docker);
+        assert_eq!(docker.pod_uid, None);
+
+        let id = "a".repeat(64);
+        let podman = parse_container(&format!("0::/machine.slice/libpod-{}.scope/container", id));
+        let podman = podman.unwrap();
+        assert_eq!(
+            (podman.runtime, podman.short_id.len()),
+            (ContainerRuntime::Podman, 12)
+        );
+        assert_eq!(podman.id, id);
+
+        // conmon supervises the container from a scope of its own.
+        assert_eq!(
+            parse_container("0::/machine.slice/libpod-conmon-abc.scope"),
+            None
+        );
+        assert_eq!(parse_container("0::/system.slice/containerd.service"), None);
+
+        let lxc = parse_container("0::/lxc.payload.web/system.slice/nginx.service").unwrap();
+        assert_eq!(
+            (lxc.runtime, lxc.id.as_str()),
+            (ContainerRuntime::Lxc, "web")
+        );
+    }
+
+    #[test]
+    fn test_parse_pod() {
+        let systemd = "0::/kubepods.slice/kubepods-burstable.slice/\
+                       kubepods-burstable-pod1b2c_33d4.slice/cri-containerd-9f8e7d.scope\n";
+        let info = parse_container(systemd).unwrap();
+        assert_eq!(info.runtime, ContainerRuntime::Containerd);
+        assert_eq!(info.pod_uid.as_deref(), Some("1b2c-33d4"));
+        assert_eq!(info.qos_class.as_deref(), Some("burstable"));
+
+        let cgroupfs = "12:pids:/kubepods/pod1b2c-33d4/9f8e7d\n";
+        let info = parse_container(cgroupfs).unwrap();
+        assert_eq!(info.runtime, ContainerRuntime::Kubernetes);
+        assert_eq!(info.pod_uid.as_deref(), Some("1b2c-33d4"));
+        assert_eq!(info.qos_class.as_deref(), Some("guaranteed"));
+    }
+}
@@ -0,0 +1,233 @@
+use super::state::annotation;
+use crate::adapters::hostfs::HostFs;
+use crate::core::models::{PodInfo, PodOwner};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const POD_LOGS: &str = "/var/log/pods";
+const KUBELET_PODS: &str = "/var/lib/kubelet/pods";
+const CHECKPOINTS: &str = "/var/lib/kubelet/checkpoints";
+
+// The alphabet Kubernetes draws generated name suffixes from; it has no
+// vowels, so real words rarely match.
+const GENERATED: &str = "bcdfghjklmnpqrstvwxz2456789";
+
+/// Explains pod `uid` from what the node keeps about it: the kubelet's log
+/// directory, named `<namespace>_<name>_<uid>` and holding one
+/// `<restart>.log` per container run; checkpointed pod objects, which name
+/// the owner; and the runtime's annotations on the container (`cri`).
+pub fn resolve_pod(
+    host: &HostFs,
+    uid: &str,
+    container: Option<&str>,
+    cri: Option<&Value>,
+) -> Option<PodInfo> {
+    let logs = pod_log_dir(host, uid);
+    let checkpoint = checkpoint(host, uid);
+    let meta = |key: &str| {
+        checkpoint
+            .as_ref()
+            .and_then(|pod| pod["metadata"][key].as_str())
+            .map(str::to_string)
+    };
+    let cri_annotation = |keys: &[&str]| cri.and_then(|c| annotation(c, keys));
+
+    let namespace = logs
+        .as_ref()
+        .map(|(_, ns, _)| ns.clone())
+        .or_else(|| meta("namespace"))
+        .or_else(|| {
+            cri_annotation(&[
+                "io.kubernetes.cri.sandbox-namespace",
+                "io.kubernetes.pod.namespace",
+            ])
+        });
+    let name = logs
+        .as_ref()
+        .map(|(_, _, name)| name.clone())
+        .or_else(|| meta("name"))
+        .or_else(|| cri_annotation(&["io.kubernetes.cri.sandbox-name", "io.kubernetes.pod.name"]))
+        .or_else(|| hosts_name(host, uid));
+    let container = container
+        .map(str::to_string)
+        .or_else(|| logs.as_ref().and_then(|(dir, _, _)| only_container(dir)));
+    let restart_count = logs
+        .as_ref()
+        .zip(container.as_deref())
+        .and_then(|((dir, _, _), c)| last_run(&dir.join(c)))
+        .or_else(|| cri.and_then(crio_attempt));
+    let owner = checkpoint
+        .as_ref()
+        .and_then(owner_reference)
+        .or_else(|| name.as_deref().and_then(infer_owner));
+
+    let pod = PodInfo {
+        namespace,
+        name,
+        container,
+        owner,
+        restart_count,
+    };
+    (pod != PodInfo::default()).then_some(pod)
+}
+
+fn pod_log_dir(host: &HostFs, uid: &str) -> Option<(PathBuf, String, String)> {
+    let suffix = format!("_{}", uid);
+    fs::read_dir(host.path(POD_LOGS))
+        .ok()?
+        .flatten()
+        .find_map(|entry| {
+            let dir_name = entry.file_name().to_string_lossy().into_owned();
+            let (ns, name) = dir_name.strip_suffix(&suffix)?.split_once('_')?;
+            Some((entry.path(), ns.to_string(), name.to_string()))
+        })
+}
+
+fn only_container(dir: &Path) -> Option<String> {
+    let mut names = fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .map(|e| e.file_name().to_string_lossy().into_owned());
+    let first = names.next()?;
+    names.next().is_none().then_some(first)
+}
+
+// The kubelet keeps the current and previous run's logs, `<n>.log`; the
+// highest `n` is the restart count.
+fn last_run(dir: &Path) -> Option<u32> {
+    fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .filter_map(|e| e.file_name().to_str()?.strip_suffix(".log")?.parse().ok())
+        .max()
+}
+
+// CRI-O records the CRI metadata, `{"name":"api","attempt":2}`, as a JSON
+// string.
+fn crio_attempt(config: &Value) -> Option<u32> {
+    let metadata = annotation(config, &["io.kubernetes.cri-o.Metadata"])?;
+    let metadata: Value = serde_json::from_str(&metadata).ok()?;
+    Some(metadata["attempt"].as_u64()? as u32)
+}
+
+fn checkpoint(host: &HostFs, uid: &str) -> Option<Value> {
+    fs::read_dir(host.path(CHECKPOINTS))
+        .ok()?
+        .flatten()
+        .filter_map(|e| serde_json::from_str::<Value>(&fs::read_to_string(e.path()).ok()?).ok())
+        .find(|pod| pod["metadata"]["uid"] == uid)
+}
+
+// A Deployment's pods are owned by a ReplicaSet named for the Deployment plus
+// the template hash the pods are labelled with.
+fn owner_reference(pod: &Value) -> Option<PodOwner> {
+    let refs = pod["metadata"]["ownerReferences"].as_array()?;
+    let owner = refs
+        .iter()
+        .find(|r| r["controller"] == true)
+        .or_else(|| refs.first())?;
+    let (kind, name) = (owner["kind"].as_str()?, owner["name"].as_str()?);
+    let deployment = pod["metadata"]["labels"]["pod-template-hash"]
+        .as_str()
+        .filter(|_| kind == "ReplicaSet")
+        .and_then(|hash| name.strip_suffix(hash)?.strip_suffix('-'));
+    let (kind, name) = match deployment {
+        Some(deployment) => ("Deployment", deployment),
+        None => (kind, name),
+    };
+    Some(PodOwner {
+        kind: kind.to_string(),
+        name: name.to_string(),
+        inferred: false,
+    })
+}
+
+// `<deployment>-<template hash>-<suffix>` is distinctive enough to guess;
+// DaemonSet, Job and bare ReplicaSet pods share the shorter
+// `<name>-<suffix>` and are left alone.
+fn infer_owner(pod_name: &str) -> Option<PodOwner> {
+    let generated = |s: &str| s.chars().all(|c| GENERATED.contains(c));
+    let (rest, suffix) = pod_name.rsplit_once('-')?;
+    let (base, hash) = rest.rsplit_once('-')?;
+    let matches = suffix.len() == 5
+        && generated(suffix)
+        && (6..=10).contains(&hash.len())
+        && generated(hash)
+        && !base.is_empty();
+    matches.then(|| PodOwner {
+        kind: "Deployment".to_string(),
+        name: base.to_string(),
+        inferred: true,
+    })
+}
+
+// The kubelet's hosts file ends its fixed block with a line mapping the pod
+// IP to the pod's hostname, which is its name unless the spec overrides it.
+fn hosts_name(host: &HostFs, uid: &str) -> Option<String> {
+    let hosts = fs::read_to_string(host.path(KUBELET_PODS).join(uid).join("etc-hosts")).ok()?;
+    hosts
+        .lines()
+        .filter(|l| !l.trim_start().starts_with('#'))
+        .filter_map(|l| l.split_whitespace().nth(1))
+        .find(|name| *name != "localhost" && !name.starts_with("ip6-"))
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::testutil::TempTree;
+
+    const UID: &str = "1b2c3d4e-0000-4a5b-9c8d-7e6f5a4b3c2d";
+
+    #[test]
+    fn test_resolve_from_pod_logs() {
+        let tree = TempTree::new("pod-logs");
+        let logs = format!("var/log/pods/shop_api-7d9f8b6c5-x2x9k_{}/api", UID);
+        for log in ["2.log", "3.log", "3.log.20240502-091428"] {
+            tree.write(&format!("{}/{}", logs, log), "");
+        }
+
+        let pod = resolve_pod(&tree.host(), UID, None, None).unwrap();
+        assert_eq!(pod.namespace.as_deref(), Some("shop"));
+        assert_eq!(pod.name.as_deref(), Some("api-7d9f8b6c5-x2x9k"));
+        assert_eq!(pod.container.as_deref(), Some("api"));
+        assert_eq!(pod.restart_count, Some(3));
+        let owner = pod.owner.unwrap();
+        assert_eq!(
+            (owner.kind.as_str(), owner.name.as_str()),
+            ("Deployment", "api")
+        );
+        assert!(owner.inferred);
+    }
+
+    #[test]
+    fn test_resolve_from_checkpoint() {
+        let tree = TempTree::new("pod-checkpoint");
+        let pod = format!(
+            r#"{{"metadata":{{"name":"node-exporter-7xk2p","namespace":"monitoring","uid":"{}",
+               "ownerReferences":[{{"kind":"DaemonSet","name":"node-exporter","controller":true}}]}}}}"#,
+            UID
+        );
+        tree.write(&format!("var/lib/kubelet/checkpoints/Pod{}.json", UID), pod);
+
+        let pod = resolve_pod(&tree.host(), UID, Some("exporter"), None).unwrap();
+        assert_eq!(
+            pod.label().as_deref(),
+            Some("pod monitoring/node-exporter-7xk2p, DaemonSet node-exporter")
+        );
+        assert_eq!(pod.container.as_deref(), Some("exporter"));
+        assert_eq!(pod.restart_count, None);
+    }
+
+    #[test]
+    fn test_infer_owner() {
+        assert_eq!(infer_owner("node-exporter-7xk2p"), None);
+        assert_eq!(infer_owner("web-5"), None);
+        assert_eq!(
+            infer_owner("coredns-5d78c9869d-4pgq2").unwrap().name,
+            "coredns"
+        );
+    }
+}
@@ -0,0 +1,142 @@
+use super::{docker, pod};
+use crate::adapters::hostfs::HostFs;
+use crate::core::models::{ContainerInfo, ContainerRuntime};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+#[cfg(target_os = "linux")]
+const DOCKER_SOCKET: &str = "/var/run/docker.sock";
+const DOCKER_CONTAINERS: &str = "/var/lib/docker/containers";
+const CONTAINERS_STORAGE: &str = "/var/lib/containers/storage/overlay-containers";
+const CONTAINERD_TASKS: &str = "/run/containerd/io.containerd.runtime.v2.task";
+
+/// Fills in the container's name and image from the state its runtime keeps
+/// on the host, without calling the runtime's CLI. Docker containers also get
+/// their lifecycle state, from the engine's API when it is reachable, and
+/// containers in a Kubernetes pod get the pod.
+pub fn load_state(host: &HostFs, info: &mut ContainerInfo) {
+    let mut cri = None;
+    let (name, image) = match info.runtime {
+        ContainerRuntime::Docker => match docker_config(host, &info.id) {
+            Some(config) => {
+                info.state = Some(docker::parse_state(&config));
+                (
+                    string(&config, "/Name").map(|n| n.trim_start_matches('/').to_string()),
+                    string(&config, "/Config/Image"),
+                )
+            }
+            None => (None, None),
+        },
+        ContainerRuntime::Podman => podman(host, &info.id),
+        ContainerRuntime::Lxc => (Some(info.id.clone()), None),
+        ContainerRuntime::CriO | ContainerRuntime::Containerd | ContainerRuntime::Kubernetes => {
+            cri = cri_config(host, info.runtime, &info.id);
+            let annotation = |keys| cri.as_ref().and_then(|c| annotation(c, keys));
+            (
+                annotation(&[
+                    "io.kubernetes.cri.container-name",
+                    "io.kubernetes.container.name",
+                ]),
+                annotation(&[
+                    "io.kubernetes.cri.image-name",
+                    "io.kubernetes.cri-o.ImageName",
+                ]),
+            )
+        }
+    };
+    info.name = name;
+    info.image = image;
+    if let Some(uid) = &info.pod_uid {
+        info.pod = pod::resolve_pod(host, uid, info.name.as_deref(), cri.as_ref());
+    }
+}
+
+fn read_json(path: &Path) -> Option<Value> {
+    serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+}
+
+fn string(value: &Value, pointer: &str) -> Option<String> {
+    value.pointer(pointer)?.as_str().map(str::to_string)
+}
+
+/// The first of `keys` set among an OCI config's annotations.
+pub(super) fn annotation(config: &Value, keys: &[&str]) -> Option<String> {
+    keys.iter()
+        .find_map(|key| config["annotations"][key].as_str())
+        .map(str::to_string)
+}
+
+// The engine's API when its socket is reachable, otherwise the copy of the
+// same document it saves to disk. Either way `Name` carries a leading slash
+// and `Config.Image` is the reference the container was created from.
+fn docker_config(host: &HostFs, id: &str) -> Option<Value> {
+    #[cfg(target_os = "linux")]
+    if let Ok(config) = super::engine::inspect(&host.path(DOCKER_SOCKET), id) {
+        return Some(config);
+    }
+    read_json(&host.path(DOCKER_CONTAINERS).join(id).join("config.v2.json"))
+}
+
+// containers/storage keeps one index for all containers; podman stores its
+// image name inside the entry's `metadata`, itself a JSON string.
+fn podman(host: &HostFs, id: &str) -> (Option<String>, Option<String>) {
+    let index = read_json(&host.path(CONTAINERS_STORAGE).join("containers.json"));
+    let Some(entry) = index
+        .as_ref()
+        .and_then(Value::as_array)
+        .and_then(|entries| entries.iter().find(|e| e["id"] == id))
+    else {
+        return (None, None);
+    };
+    let image = entry["metadata"]
+        .as_str()
+        .and_then(|m| serde_json::from_str::<Value>(m).ok())
+        .and_then(|m| string(&m, "/image-name"));
+    (string(entry, "/names/0"), image)
+}
+
+// The OCI config of a CRI container. containerd keeps each task's bundle
+// under its namespace (`k8s.io` for the CRI plugin, `moby` for docker);
+// CRI-O keeps it in containers/storage.
+fn cri_config(host: &HostFs, runtime: ContainerRuntime, id: &str) -> Option<Value> {
+    let containerd = || {
+        fs::read_dir(host.path(CONTAINERD_TASKS))
+            .ok()?
+            .flatten()
+            .find_map(|ns| read_json(&ns.path().join(id).join("config.json")))
+    };
+    let crio = || {
+        read_json(
+            &host
+                .path(CONTAINERS_STORAGE)
+                .join(id)
+                .join("userdata/config.json"),
+        )
+    };
+    match runtime {
+        ContainerRuntime::Containerd => containerd(),
+        ContainerRuntime::CriO => crio(),
+        _ => containerd().or_else(crio),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::testutil::TempTree;
+
+    #[test]
+    fn test_load_podman_state() {
+        let tree = TempTree::new("podman");
+        tree.write(
+            &format!("{}/containers.json", CONTAINERS_STORAGE.trim_start_matches('/')),
+            r#"[{"id":"9f8e","names":["db"],"metadata":"{\"image-name\":\"docker.io/library/postgres:16\"}"}]"#,
+        );
+
+        let mut info = ContainerInfo::new(ContainerRuntime::Podman, "9f8e");
+        load_state(&tree.host(), &mut info);
+        assert_eq!(info.name.as_deref(), Some("db"));
+        assert_eq!(info.image.as_deref(), Some("docker.io/library/postgres:16"));
+    }
+}
@@ -1,30 +0,0 @@
-pub fn is_cron_process(_pid: u32) -> bool {
-    false
-}
-
-#[allow(dead_code)]
-fn parse_crontab_line(line: &str) -> Option<String> {
-    if line.starts_with('@') || line.split_whitespace().count() >= 5 {
-        Some(line.to_string())
-    } else {
-        None
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_parse_crontab_line() {
-        assert_eq!(
-            parse_crontab_line("* * * * * /bin/true"),
-            Some("* * * * * /bin/true".to_string())
-        );
-        assert_eq!(
-            parse_crontab_line("@daily /bin/cleanup"),
-            Some("@daily /bin/cleanup".to_string())
-        );
-        assert_eq!(parse_crontab_line("invalid"), None);
-    }
-}
@@ -0,0 +1,173 @@
+use crate::core::models::CronJob;
+use chrono::{Duration, Local, Months, NaiveDate, TimeZone};
+
+/// Entries of a crontab. System crontabs (`/etc/crontab`, `/etc/cron.d/*`)
+/// name the user in a sixth field; a user's spool file runs everything as
+/// `owner`.
+pub fn parse_crontab(file: &str, content: &str, owner: Option<&str>) -> Vec<CronJob> {
+    entries(content)
+        .filter_map(|(line, text)| {
+            let (schedule, rest) = match text.starts_with('@') {
+                true => split_fields(text, 1)?,
+                false => split_fields(text, 5)?,
+            };
+            let (user, command) = match owner {
+                Some(owner) => (owner.to_string(), rest),
+                None => {
+                    let (user, command) = split_fields(rest, 1)?;
+                    (user, command)
+                }
+            };
+            Some(CronJob {
+                file: file.to_string(),
+                line,
+                schedule,
+                user: Some(user),
+                command: command_line(command)?,
+                next_run: None,
+            })
+        })
+        .collect()
+}
+
+/// Entries of an anacrontab: `period delay job-id command`, run as root.
+/// Anacron stamps each job's last run, as `YYYYMMDD`, in
+/// `<spool>/<job-id>`; `last_run` looks it up.
+pub fn parse_anacrontab(
+    file: &str,
+    content: &str,
+    last_run: impl Fn(&str) -> Option<String>,
+) -> Vec<CronJob> {
+    entries(content)
+        .filter_map(|(line, text)| {
+            let (fields, command) = split_fields(text, 3)?;
+            let [period, delay, id] = fields.split(' ').collect::<Vec<_>>()[..] else {
+                return None;
+            };
+            let schedule = match period.parse::<u32>() {
+                Ok(1) => "every day".to_string(),
+                Ok(days) => format!("every {} days", days),
+                Err(_) if period.starts_with('@') => period.to_string(),
+                Err(_) => return None,
+            };
+            let next_run = last_run(id).and_then(|stamp| {
+                let last = NaiveDate::parse_from_str(stamp.trim(), "%Y%m%d").ok()?;
+                let due = match period {
+                    "@monthly" => last.checked_add_months(Months::new(1))?,
+                    "@yearly" | "@annually" => last.checked_add_months(Months::new(12))?,
+                    "@weekly" => last + Duration::days(7),
+                    "@daily" => last + Duration::days(1),
+                    days => last + Duration::days(days.parse().ok()?),
+                };
+                let due = due.and_hms_opt(0, 0, 0)? + Duration::minutes(delay.parse().ok()?);
+                let time = Local.from_local_datetime(&due).earliest()?.timestamp();
+                u64::try_from(time).ok()
+            });
+            Some(CronJob {
+                file: file.to_string(),
+                line,
+                schedule,
+                user: Some("root".to_string()),
+                command: command_line(command)?,
+                next_run,
+            })
+        })
+        .collect()
+}
+
+// Numbered lines that are neither blank, comments nor `NAME=value`
+// environment settings.
+fn entries(content: &str) -> impl Iterator<Item = (usize, &str)> {
+    content
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .filter(|(_, line)| {
+            let name = line.split('=').next().unwrap_or_default().trim();
+            !line.contains('=') || name.contains(char::is_whitespace)
+        })
+}
+
+// The first `n` whitespace-separated fields, joined by single spaces, and the
+// rest of the line.
+fn split_fields(text: &str, n: usize) -> Option<(String, &str)> {
+    let mut rest = text.trim_start();
+    let mut fields = Vec::with_capacity(n);
+    for _ in 0..n {
+        let end = rest.find(char::is_whitespace)?;
+        fields.push(&rest[..end]);
+        rest = rest[end..].trim_start();
+    }
+    Some((fields.join(" "), rest))
+}
+
+// Cron feeds everything after the first unescaped `%` to the command's
+// stdin, and turns `\%` into `%`.
+fn command_line(command: &str) -> Option<String> {
+    let mut line = String::new();
+    let mut chars = command.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.as_str().starts_with('%') => {
+                line.push('%');
+                chars.next();
+            }
+            '%' => break,
+            c => line.push(c),
+        }
+    }
+    let line = line.trim();
+    (!line.is_empty()).then(|| line.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_system_crontab() {
+        let content = "SHELL=/bin/sh\nMAILTO = root\n# m h dom mon dow user command\n\n\
+            0 2 * * *  root  /usr/local/bin/backup.sh --full > /var/log/backup.log 2>&1\n\
+            @hourly www-data php /srv/app/artisan schedule:run\n";
+        let jobs = parse_crontab("/etc/cron.d/backup", content, None);
+        assert_eq!(jobs.len(), 2);
+        assert_eq!(jobs[0].to_string(), "/etc/cron.d/backup:5 (0 2 * * *)");
+        assert_eq!(jobs[0].user.as_deref(), Some("root"));
+        assert_eq!(
+            jobs[0].command,
+            "/usr/local/bin/backup.sh --full > /var/log/backup.log 2>&1"
+        );
+        assert_eq!(jobs[1].schedule, "@hourly");
+        assert_eq!(jobs[1].user.as_deref(), Some("www-data"));
+    }
+
+    #[test]
+    fn test_parse_user_crontab() {
+        let content = "*/5 * * * * date +\\%s%ignored stdin\n";
+        let jobs = parse_crontab("/var/spool/cron/crontabs/alice", content, Some("alice"));
+        assert_eq!(jobs[0].schedule, "*/5 * * * *");
+        assert_eq!(jobs[0].command, "date +%s");
+        assert_eq!(jobs[0].user.as_deref(), Some("alice"));
+    }
+
+    #[test]
+    fn test_parse_anacrontab() {
+        let content = "START_HOURS_RANGE=3-22\n\
+            1\t5\tcron.daily\trun-parts --report /etc/cron.daily\n\
+            @monthly 15 cron.monthly run-parts --report /etc/cron.monthly\n";
+        let jobs = parse_anacrontab("/etc/anacrontab", content, |id| {
+            (id == "cron.daily").then(|| "20240502\n".to_string())
+        });
+        assert_eq!(jobs[0].to_string(), "/etc/anacrontab:2 (every day)");
+        assert_eq!(jobs[0].command, "run-parts --report /etc/cron.daily");
+        let due = NaiveDate::from_ymd_opt(2024, 5, 3)
+            .unwrap()
+            .and_hms_opt(0, 5, 0)
+            .unwrap();
+        let due = Local.from_local_datetime(&due).earliest().unwrap();
+        assert_eq!(jobs[0].next_run, Some(due.timestamp() as u64));
+        assert_eq!(jobs[1].schedule, "@monthly");
+        assert_eq!(jobs[1].next_run, None);
+    }
+}
@@ -0,0 +1,182 @@
+mod crontab;
+mod schedule;
+
+use crate::adapters::hostfs::HostFs;
+use crate::adapters::proc::ProcFs;
+use crate::adapters::source::detect::has_ancestor;
+use crate::core::models::CronJob;
+use chrono::{Local, TimeZone};
+use std::fs;
+use std::path::Path;
+
+pub use crontab::{parse_anacrontab, parse_crontab};
+pub use schedule::Calendar;
+
+const SYSTEM_CRONTABS: [&str; 2] = ["/etc/crontab", "/etc/cron.d"];
+// Debian keeps user crontabs in `crontabs/`, Red Hat directly in the spool.
+const USER_SPOOLS: [&str; 2] = ["/var/spool/cron/crontabs", "/var/spool/cron"];
+const ANACRONTAB: &str = "/etc/anacrontab";
+const ANACRON_SPOOL: &str = "/var/spool/anacron";
+
+const DAEMONS: [&str; 4] = ["cron", "crond", "anacron", "fcron"];
+const SHELLS: [&str; 3] = ["sh", "bash", "dash"];
+
+/// Every entry of the host's crontabs, user spools and anacrontab.
+pub fn load_cron_jobs(host: &HostFs) -> Vec<CronJob> {
+    let mut jobs = Vec::new();
+    for crontab in SYSTEM_CRONTABS.iter().flat_map(|p| files(host, p)) {
+        if let Some((file, content)) = read(host, &crontab) {
+            jobs.extend(parse_crontab(&file, &content, None));
+        }
+    }
+    for crontab in USER_SPOOLS.iter().flat_map(|p| files(host, p)) {
+        if let Some((file, content)) = read(host, &crontab) {
+            let user = file.rsplit('/').next().unwrap_or_default().to_string();
+            jobs.extend(parse_crontab(&file, &content, Some(&user)));
+        }
+    }
+    if let Some((file, content)) = read(host, ANACRONTAB) {
+        jobs.extend(parse_anacrontab(&file, &content, |id| {
+            fs::read_to_string(host.path(ANACRON_SPOOL).join(id)).ok()
+        }));
+    }
+    jobs
+}
+
+/// Names cron daemons go by, including the per-job children they fork.
+pub fn is_cron_daemon(name: &str) -> bool {
+    DAEMONS.contains(&name.to_ascii_lowercase().as_str())
+}
+
+/// Whether a cron daemon is among `pid`'s ancestors.
+pub fn is_cron_process(procfs: &ProcFs, pid: u32) -> bool {
+    has_ancestor(procfs, pid, |comm, _| is_cron_daemon(comm))
+}
+
+/// The job that launched a process, given the command lines of the process
+/// and its ancestors below the cron daemon. Cron runs each job as
+/// `sh -c <command>`, so the shell's script names it exactly; otherwise the
+/// process must be one of the simple commands in the job's command line.
+/// Jobs for a different `user` are skipped.
+pub fn match_job(jobs: &[CronJob], commands: &[&[String]], user: Option<&str>) -> Option<CronJob> {
+    let mut best: Option<(u8, &CronJob)> = None;
+    for job in jobs {
+        if user.is_some() && job.user.is_some() && job.user.as_deref() != user {
+            continue;
+        }
+        let score = commands
+            .iter()
+            .map(|argv| score(job, argv))
+            .max()
+            .unwrap_or(0);
+        if score > best.map_or(0, |(s, _)| s) {
+            best = Some((score, job));
+        }
+    }
+    let mut job = best?.1.clone();
+    job.next_run = job.next_run.or_else(|| next_run(&job.schedule));
+    Some(job)
+}
+
+fn score(job: &CronJob, argv: &[String]) -> u8 {
+    if let [.., shell, flag, script] = argv {
+        let shell = shell.rsplit('/').next().unwrap_or_default();
+        if SHELLS.contains(&shell) && flag == "-c" && script.trim() == job.command {
+            return 3;
+        }
+    }
+    if argv.join(" ") == job.command {
+        return 2;
+    }
+    let simple = simple_commands(&job.command);
+    let matches = |argv: &[String]| {
+        simple.iter().any(|tokens| {
+            tokens.len() == argv.len() && tokens.iter().zip(argv).all(|(t, a)| same_arg(t, a))
+        })
+    };
+    // A script's shebang interpreter runs it with the script as argv[1].
+    u8::from(matches(argv) || argv.len() > 1 && matches(&argv[1..]))
+}
+
+// Words of each command in `a && b | c; d`, up to any redirection.
+fn simple_commands(command: &str) -> Vec<Vec<&str>> {
+    command
+        .split(['&', '|', ';'])
+        .map(|part| {
+            part.split_whitespace()
+                .take_while(|w| !w.contains(['<', '>']))
+                .collect::<Vec<_>>()
+        })
+        .filter(|words| !words.is_empty())
+        .collect()
+}
+
+// A bare program name in the crontab is found on `PATH`.
+fn same_arg(token: &str, arg: &str) -> bool {
+    token == arg || !token.contains('/') && Path::new(arg).file_name() == Some(token.as_ref())
+}
+
+fn next_run(schedule: &str) -> Option<u64> {
+    let now = Local::now().naive_local();
+    let next = Calendar::parse(schedule)?.next_after(now)?;
+    let time = Local.from_local_datetime(&next).earliest()?.timestamp();
+    u64::try_from(time).ok()
+}
+
+// `/etc/crontab` itself, or the regular files directly in a directory such
+// as `/etc/cron.d`, skipping editor and package manager leftovers.
+fn files(host: &HostFs, path: &str) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(host.path(path)) else {
+        return vec![path.to_string()];
+    };
+    let mut files: Vec<String> = entries
+        .flatten()
+        .filter(|e| e.file_type().is_ok_and(|t| t.is_file()))
+        .filter_map(|e| e.file_name().into_string().ok())
+        .filter(|name| !name.starts_with('.') && !name.contains(['~', '#']))
+        .filter(|name| !name.ends_with(".dpkg-old") && !name.ends_with(".rpmsave"))
+        .map(|name| format!("{}/{}", path, name))
+        .collect();
+    files.sort();
+    files
+}
+
+fn read(host: &HostFs, file: &str) -> Option<(String, String)> {
+    let content = fs::read_to_string(host.path(file)).ok()?;
+    Some((file.to_string(), content))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn argv(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_match_job() {
+        let jobs = parse_crontab(
+            "/etc/cron.d/backup",
+            "0 2 * * * root cd /srv && backup.sh --full >/dev/null 2>&1\n\
+             0 3 * * * root /usr/bin/find /tmp -mtime +7 -delete\n",
+            None,
+        );
+        let shell = argv(&[
+            "/bin/sh",
+            "-c",
+            "cd /srv && backup.sh --full >/dev/null 2>&1",
+        ]);
+        let script = argv(&["/bin/bash", "/usr/local/bin/backup.sh", "--full"]);
+
+        let job = match_job(&jobs, &[&script, &shell], Some("root")).unwrap();
+        assert_eq!(job.location(), "/etc/cron.d/backup:1");
+        assert!(job.next_run.is_some());
+        // The script alone, once its shell has exec'd it away.
+        assert_eq!(match_job(&jobs, &[&script], None).map(|j| j.line), Some(1));
+        let find = argv(&["/usr/bin/find", "/tmp", "-mtime", "+7", "-delete"]);
+        assert_eq!(match_job(&jobs, &[&find], None).map(|j| j.line), Some(2));
+        assert_eq!(match_job(&jobs, &[&find], Some("alice")), None);
+        assert_eq!(match_job(&jobs, &[&argv(&["/usr/bin/find"])], None), None);
+    }
+}
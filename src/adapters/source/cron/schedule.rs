@@ -0,0 +1,186 @@
+use chrono::{Datelike, Duration, NaiveDateTime, Timelike};
+
+const MONTHS: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+const DAYS: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+// Far enough ahead for `0 0 29 2 1`-style entries to come round again.
+const HORIZON_DAYS: i64 = 366 * 8;
+
+/// The five time fields of a crontab entry, each as a bitmask of the values
+/// it allows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Calendar {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    // Vixie cron runs a job on either matching day when both day fields
+    // are restricted, and on days matching both otherwise.
+    any_day: bool,
+}
+
+impl Calendar {
+    /// Parses `0 2 * * *` or one of the `@daily`-style shorthands. `@reboot`
+    /// has no calendar.
+    pub fn parse(spec: &str) -> Option<Self> {
+        let spec = match spec {
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            "@monthly" => "0 0 1 * *",
+            "@weekly" => "0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@hourly" => "0 * * * *",
+            spec => spec,
+        };
+        let fields: Vec<&str> = spec.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            return None;
+        };
+        let weekdays = field(weekday, 0, 7, &DAYS)?;
+        Some(Self {
+            minutes: field(minute, 0, 59, &[])?,
+            hours: field(hour, 0, 23, &[])?,
+            days: field(day, 1, 31, &[])?,
+            months: field(month, 1, 12, &MONTHS)?,
+            // Sunday is both 0 and 7.
+            weekdays: (weekdays | weekdays >> 7) & 0x7f,
+            any_day: !day.starts_with('*') && !weekday.starts_with('*'),
+        })
+    }
+
+    /// The first minute after `after` that the calendar matches.
+    pub fn next_after(&self, after: NaiveDateTime) -> Option<NaiveDateTime> {
+        let start = after.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        (0..HORIZON_DAYS).find_map(|offset| {
+            let date = start.date() + Duration::days(offset);
+            if !self.matches_day(date) {
+                return None;
+            }
+            // Only the first day starts part way through.
+            let (first_hour, first_minute) = match offset {
+                0 => (start.hour(), start.minute()),
+                _ => (0, 0),
+            };
+            (first_hour..24)
+                .filter(|h| bit(self.hours, *h))
+                .find_map(|hour| {
+                    let from = if hour == first_hour { first_minute } else { 0 };
+                    let minute = (from..60).find(|m| bit(self.minutes, *m))?;
+                    date.and_hms_opt(hour, minute, 0)
+                })
+        })
+    }
+
+    fn matches_day(&self, date: chrono::NaiveDate) -> bool {
+        if !bit(self.months, date.month()) {
+            return false;
+        }
+        let day = bit(self.days, date.day());
+        let weekday = bit(self.weekdays, date.weekday().num_days_from_sunday());
+        match self.any_day {
+            true => day || weekday,
+            false => day && weekday,
+        }
+    }
+}
+
+fn bit(mask: u64, value: u32) -> bool {
+    mask & (1 << value) != 0
+}
+
+// `*`, `5`, `1-5`, `*/15`, `10-50/10`, `mon-fri`, and comma lists of those.
+fn field(spec: &str, min: u32, max: u32, names: &[&str]) -> Option<u64> {
+    let value = |s: &str| -> Option<u32> {
+        let lower = s.to_ascii_lowercase();
+        let value = match names.iter().position(|n| *n == lower) {
+            Some(index) => index as u32 + if min == 1 { 1 } else { 0 },
+            None => s.parse().ok()?,
+        };
+        (min..=max).contains(&value).then_some(value)
+    };
+    let mut mask = 0;
+    for part in spec.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, step.parse().ok().filter(|s| *s > 0)?),
+            None => (part, 1),
+        };
+        let (start, end) = match range {
+            "*" => (min, max),
+            range => match range.split_once('-') {
+                Some((start, end)) => (value(start)?, value(end)?),
+                // `5/10` runs from 5 to the end of the range.
+                None if step > 1 => (value(range)?, max),
+                None => (value(range)?, value(range)?),
+            },
+        };
+        for v in (start..=end).step_by(step as usize) {
+            mask |= 1 << v;
+        }
+    }
+    Some(mask)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn next(spec: &str, after: &str) -> NaiveDateTime {
+        Calendar::parse(spec)
+            .unwrap()
+            .next_after(at(after))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_next_after() {
+        assert_eq!(
+            next("0 2 * * *", "2024-05-02 09:14"),
+            at("2024-05-03 02:00")
+        );
+        assert_eq!(
+            next("0 2 * * *", "2024-05-02 01:59"),
+            at("2024-05-02 02:00")
+        );
+        assert_eq!(
+            next("*/15 * * * *", "2024-05-02 09:14"),
+            at("2024-05-02 09:15")
+        );
+        assert_eq!(
+            next("30 9 * * mon-fri", "2024-05-03 10:00"),
+            at("2024-05-06 09:30")
+        );
+        assert_eq!(next("@monthly", "2024-05-02 09:14"), at("2024-06-01 00:00"));
+        assert_eq!(
+            next("0 0 29 2 *", "2024-03-01 00:00"),
+            at("2028-02-29 00:00")
+        );
+    }
+
+    #[test]
+    fn test_day_fields() {
+        // Either the 1st or a Sunday once both are restricted.
+        assert_eq!(
+            next("0 0 1 * 7", "2024-05-02 00:00"),
+            at("2024-05-05 00:00")
+        );
+        // Only Sundays when the day of month is unrestricted.
+        assert_eq!(
+            next("0 0 * * 0", "2024-05-02 00:00"),
+            at("2024-05-05 00:00")
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects() {
+        assert_eq!(Calendar::parse("@reboot"), None);
+        assert_eq!(Calendar::parse("61 * * * *"), None);
+        assert_eq!(Calendar::parse("* * * *"), None);
+        assert_eq!(Calendar::parse("*/0 * * * *"), None);
+    }
+}
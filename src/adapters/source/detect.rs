@@ -1,4 +1,5 @@
-use crate::adapters::source::{container, cron, git, network, service, shell, supervisor};
+use crate::adapters::proc::ProcFs;
+use crate::adapters::source::{container, cron, git, initsys, network, service, shell, supervisor};
 
 #[derive(Debug, PartialEq)]
 pub enum SourceType {
@@ -7,20 +8,27 @@ pub enum SourceType {
     Git(String),
     Cron,
     Supervisor,
+    InitService,
     Shell,
     Network(u16),
     Unknown,
 }
 
-pub fn detect_source(pid: u32, comm: &str, cwd: Option<&String>, port: Option<u16>) -> SourceType {
+pub fn detect_source(
+    procfs: &ProcFs,
+    pid: u32,
+    comm: &str,
+    cwd: Option<&String>,
+    port: Option<u16>,
+) -> SourceType {
     // Order of precedence:
     // 1. Container (highest isolation)
-    if let Some(id) = container::detect_container(pid) {
+    if let Some(id) = container::detect_container(procfs, pid) {
         return SourceType::Container(id);
     }
 
     // 2. Service Manager (Systemd/Launchd)
-    if let Some(svc) = service::get_service_info(pid) {
+    if let Some(svc) = service::get_service_info(procfs, pid) {
         return SourceType::Service(svc);
     }
 
@@ -30,14 +38,18 @@ pub fn detect_source(pid: u32, comm: &str, cwd: Option<&String>, port: Option<u1
     }
 
     // 4. Others
-    if cron::is_cron_process(pid) {
+    if cron::is_cron_process(procfs, pid) {
         return SourceType::Cron;
     }
 
-    if supervisor::is_supervisor_process(pid) {
+    if supervisor::is_supervisor_process(procfs, pid) {
         return SourceType::Supervisor;
     }
 
+    if initsys::is_supervised_process(procfs, pid) {
+        return SourceType::InitService;
+    }
+
     if shell::is_shell_process(comm) {
         return SourceType::Shell;
     }
@@ -51,6 +63,43 @@ pub fn detect_source(pid: u32, comm: &str, cwd: Option<&String>, port: Option<u1
     SourceType::Unknown
 }
 
+// How far up the process tree to look for a process manager.
+const MAX_DEPTH: usize = 16;
+
+/// Whether an ancestor of `pid` satisfies `matches`, given its name and
+/// command line. PID 1 is tested too, since a container's init may be the
+/// manager itself.
+#[cfg(target_os = "linux")]
+pub fn has_ancestor(procfs: &ProcFs, pid: u32, matches: impl Fn(&str, &[String]) -> bool) -> bool {
+    use crate::adapters::proc::linux::{cmdline, stat};
+    let mut current = stat::read_stat(procfs, pid);
+    for _ in 0..MAX_DEPTH {
+        let Some((ppid, parent)) = current
+            .filter(|s| s.ppid > 0)
+            .and_then(|s| Some((s.ppid, stat::read_stat(procfs, s.ppid)?)))
+        else {
+            return false;
+        };
+        if matches(&parent.comm, &cmdline::get_cmdline(procfs, ppid)) {
+            return true;
+        }
+        if ppid <= 1 {
+            return false;
+        }
+        current = Some(parent);
+    }
+    false
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn has_ancestor(
+    _procfs: &ProcFs,
+    _pid: u32,
+    _matches: impl Fn(&str, &[String]) -> bool,
+) -> bool {
+    false
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
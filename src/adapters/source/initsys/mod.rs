@@ -0,0 +1,55 @@
+mod pm2;
+mod rc;
+mod svdir;
+
+use crate::adapters::hostfs::HostFs;
+use crate::adapters::proc::ProcFs;
+use crate::adapters::source::detect::has_ancestor;
+use crate::core::models::InitSystem;
+use std::fs;
+
+pub use pm2::{is_pm2_daemon, pm2_app, pm2_home};
+pub use rc::{supervise_daemon, InitScripts};
+pub use svdir::{scan_dir, service_dir};
+
+/// The init system whose per-service supervisor a process is: runit's
+/// `runsv`, s6's `s6-supervise` or OpenRC's `supervise-daemon`.
+pub fn service_supervisor(name: &str) -> Option<InitSystem> {
+    match name {
+        "runsv" => Some(InitSystem::Runit),
+        "s6-supervise" => Some(InitSystem::S6),
+        "supervise-daemon" => Some(InitSystem::OpenRc),
+        _ => None,
+    }
+}
+
+/// Whether a service supervisor or the PM2 daemon is among `pid`'s
+/// ancestors.
+pub fn is_supervised_process(procfs: &ProcFs, pid: u32) -> bool {
+    has_ancestor(procfs, pid, |comm, cmd| {
+        service_supervisor(comm).is_some() || is_pm2_daemon(cmd)
+    })
+}
+
+// The pid a pid file holds.
+fn read_pid(host: &HostFs, path: &str) -> Option<u32> {
+    fs::read_to_string(host.path(path))
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+// `dir`'s entries as host paths, sorted.
+fn list(host: &HostFs, dir: &str) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(host.path(dir)) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<String> = entries
+        .flatten()
+        .filter_map(|e| e.file_name().into_string().ok())
+        .map(|name| format!("{}/{}", dir.trim_end_matches('/'), name))
+        .collect();
+    paths.sort();
+    paths
+}
@@ -0,0 +1,125 @@
+use super::{list, read_pid};
+use crate::adapters::hostfs::HostFs;
+use crate::core::models::{InitService, InitSystem};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+// Run in the foreground, as a container's PID 1, these manage the apps
+// themselves rather than through a God Daemon.
+const RUNTIMES: [&str; 2] = ["pm2-runtime", "pm2-docker"];
+
+/// Whether a command line is PM2's daemon, which retitles itself
+/// `PM2 v5.3.0: God Daemon (/root/.pm2)`, or `pm2-runtime`.
+pub fn is_pm2_daemon(cmd: &[String]) -> bool {
+    cmd.first()
+        .is_some_and(|title| title.starts_with("PM2 ") && title.contains("God Daemon"))
+        || cmd.iter().take(2).any(|arg| {
+            Path::new(arg)
+                .file_name()
+                .is_some_and(|f| RUNTIMES.iter().any(|r| f == *r))
+        })
+}
+
+/// PM2's home directory: named in its daemon's title, else the app's
+/// `PM2_HOME`, else `.pm2` in the app's `HOME`.
+pub fn pm2_home(daemon: &[String], env: &[String]) -> Option<String> {
+    let var = |key: &str| {
+        env.iter()
+            .find_map(|v| v.strip_prefix(key)?.strip_prefix('='))
+    };
+    daemon
+        .first()
+        .and_then(|title| title.rsplit_once('(')?.1.strip_suffix(')'))
+        .or_else(|| var("PM2_HOME"))
+        .map(str::to_string)
+        .or_else(|| Some(format!("{}/.pm2", var("HOME")?.trim_end_matches('/'))))
+}
+
+/// The PM2 app whose pid file under `home` holds `pid`, the daemon's child.
+/// Its settings come from `dump.pm2`, the process list `pm2 save` wrote
+/// and `pm2 resurrect` restores.
+pub fn pm2_app(host: &HostFs, home: &str, pid: u32) -> Option<InitService> {
+    let home = home.trim_end_matches('/');
+    let pidfile = list(host, &format!("{}/pids", home))
+        .into_iter()
+        .find(|path| path.ends_with(".pid") && read_pid(host, path) == Some(pid))?;
+    // `<name>-<pm_id>.pid`
+    let stem = pidfile.rsplit('/').next()?.strip_suffix(".pid")?;
+    let (name, id) = stem.rsplit_once('-')?;
+
+    let dump = format!("{}/dump.pm2", home);
+    let apps: Vec<Value> = fs::read_to_string(host.path(&dump))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default();
+    let app = apps
+        .iter()
+        .find(|app| app["name"].as_str() == Some(name) && app["pm_id"].as_u64() == id.parse().ok());
+    let (config, restart) = match app {
+        Some(app) => (dump, restart_policy(app)),
+        // Started but never saved: only the pid file knows of it.
+        None => (pidfile.clone(), "always, not saved".to_string()),
+    };
+    Some(InitService {
+        manager: InitSystem::Pm2,
+        name: name.to_string(),
+        config,
+        pidfile: Some(pidfile),
+        restart,
+    })
+}
+
+fn restart_policy(app: &Value) -> String {
+    if app["autorestart"] == Value::Bool(false) {
+        return "never".to_string();
+    }
+    match app["max_restarts"].as_u64() {
+        Some(max) => format!("always, max {} unstable restarts", max),
+        None => "always".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::testutil::TempTree;
+
+    #[test]
+    fn test_pm2_app() {
+        let tree = TempTree::new("pm2");
+        tree.write("home/deploy/.pm2/pids/api-0.pid", "4101")
+            .write("home/deploy/.pm2/pids/cron-job-1.pid", "4102")
+            .write(
+                "home/deploy/.pm2/dump.pm2",
+                r#"[{"name":"api","pm_id":0,"autorestart":true,"max_restarts":16},
+                    {"name":"cron-job","pm_id":1,"autorestart":false}]"#,
+            );
+        let host = tree.host();
+        let daemon = vec!["PM2 v5.3.0: God Daemon (/home/deploy/.pm2)".to_string()];
+        assert!(is_pm2_daemon(&daemon));
+        let home = pm2_home(&daemon, &[]).unwrap();
+        let api = pm2_app(&host, &home, 4101).unwrap();
+        let job = pm2_app(&host, &home, 4102).unwrap();
+        let unknown = pm2_app(&host, &home, 4103);
+
+        assert_eq!(api.to_string(), "pm2 api (/home/deploy/.pm2/dump.pm2)");
+        assert_eq!(api.restart, "always, max 16 unstable restarts");
+        assert_eq!(job.name, "cron-job");
+        assert_eq!(job.restart, "never");
+        assert_eq!(unknown, None);
+    }
+
+    #[test]
+    fn test_pm2_runtime() {
+        let runtime: Vec<String> = ["node", "/usr/local/bin/pm2-runtime", "start", "app.js"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert!(is_pm2_daemon(&runtime));
+        assert!(!is_pm2_daemon(&["node".to_string(), "pm2.js".to_string()]));
+        let env = ["HOME=/home/node/".to_string()];
+        assert_eq!(pm2_home(&runtime, &env).as_deref(), Some("/home/node/.pm2"));
+        assert_eq!(pm2_home(&runtime, &[]), None);
+    }
+}
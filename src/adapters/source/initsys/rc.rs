@@ -0,0 +1,212 @@
+use super::{list, read_pid};
+use crate::adapters::hostfs::HostFs;
+use crate::core::models::{InitService, InitSystem};
+use std::fs;
+use std::path::Path;
+
+const INIT_D: &str = "/etc/init.d";
+const OPENRC: &str = "/run/openrc";
+// Present when systemd is the init system, which runs init scripts as units
+// of its own.
+const SYSTEMD: &str = "/run/systemd/system";
+// Daemons leave pid files directly in the run directory or in one of its
+// own there. `/var/run` is an absolute symlink to it, which under a host
+// root would lead to the inspecting machine's own `/run`.
+const RUN_DIR: &str = "/run";
+
+/// The host's init scripts and the pid files their daemons left, read once
+/// and matched against each process.
+#[derive(Debug, Default)]
+pub struct InitScripts {
+    // `(name, content)` of each script.
+    scripts: Vec<(String, String)>,
+    // `(path, pid)` of each pid file.
+    pidfiles: Vec<(String, u32)>,
+    // OpenRC's started services with the pid files `start-stop-daemon`
+    // recorded for them; `None` when OpenRC is not the init system.
+    openrc: Option<Vec<(String, Vec<String>)>>,
+}
+
+impl InitScripts {
+    pub fn load(host: &HostFs) -> Self {
+        if host.path(SYSTEMD).is_dir() {
+            return Self::default();
+        }
+        let scripts = list(host, INIT_D)
+            .into_iter()
+            .filter_map(|path| {
+                let content = fs::read_to_string(host.path(&path)).ok()?;
+                Some((path.rsplit('/').next()?.to_string(), content))
+            })
+            .collect();
+        let pidfiles = list(host, RUN_DIR)
+            .into_iter()
+            .flat_map(|path| match host.path(&path).is_dir() {
+                true => list(host, &path),
+                false => vec![path],
+            })
+            .filter(|path| path.ends_with(".pid"))
+            .filter_map(|path| Some((path.clone(), read_pid(host, &path)?)))
+            .collect();
+        let openrc = host.path(OPENRC).is_dir().then(|| {
+            list(host, &format!("{}/started", OPENRC))
+                .into_iter()
+                .filter_map(|started| {
+                    let service = started.rsplit('/').next()?.to_string();
+                    let daemons = list(host, &format!("{}/daemons/{}", OPENRC, service));
+                    let pidfiles = daemons
+                        .iter()
+                        .filter_map(|file| fs::read_to_string(host.path(file)).ok())
+                        .flat_map(|record| {
+                            record
+                                .lines()
+                                .filter_map(|l| l.strip_prefix("pidfile="))
+                                .map(str::to_string)
+                                .collect::<Vec<_>>()
+                        })
+                        .collect();
+                    Some((service, pidfiles))
+                })
+                .collect()
+        });
+        Self {
+            scripts,
+            pidfiles,
+            openrc,
+        }
+    }
+
+    /// The init script whose daemon wrote a pid file holding one of `pids`,
+    /// the process's own first, then its parent's. Under OpenRC the service must be started,
+    /// and is known by the pid file recorded for it or named in its script;
+    /// otherwise the script names the pid file or is named after it. Neither
+    /// restarts a daemon that exits.
+    pub fn find(&self, pids: &[u32]) -> Option<InitService> {
+        let (pidfile, _) = pids
+            .iter()
+            .find_map(|pid| self.pidfiles.iter().find(|(_, p)| p == pid))?;
+        let names = |name: &str| {
+            self.scripts
+                .iter()
+                .any(|(script, content)| script == name && mentions(content, pidfile))
+        };
+        let (manager, name) = match &self.openrc {
+            Some(started) => {
+                let (name, _) = started
+                    .iter()
+                    .find(|(_, recorded)| recorded.contains(pidfile))
+                    .or_else(|| started.iter().find(|(name, _)| names(name)))?;
+                (InitSystem::OpenRc, name)
+            }
+            None => {
+                let stem = Path::new(pidfile).file_stem()?.to_str()?;
+                let (name, _) = self
+                    .scripts
+                    .iter()
+                    .find(|(_, content)| mentions(content, pidfile))
+                    .or_else(|| self.scripts.iter().find(|(name, _)| name == stem))?;
+                (InitSystem::SysV, name)
+            }
+        };
+        Some(InitService {
+            manager,
+            name: name.clone(),
+            config: format!("{}/{}", INIT_D, name),
+            pidfile: Some(pidfile.clone()),
+            restart: "never".to_string(),
+        })
+    }
+}
+
+/// The OpenRC service that `supervise-daemon <service> --start ...` runs,
+/// respawned within the limits it was given.
+pub fn supervise_daemon(cmd: &[String]) -> Option<InitService> {
+    let name = cmd.get(1).filter(|arg| !arg.starts_with('-'))?;
+    let option = |flag: &str| {
+        cmd.iter()
+            .enumerate()
+            .find_map(|(i, arg)| match arg == flag {
+                true => cmd.get(i + 1).cloned(),
+                false => Some(arg.strip_prefix(flag)?.strip_prefix('=')?.to_string()),
+            })
+    };
+    let mut restart = "respawn".to_string();
+    if let Some(max) = option("--respawn-max").filter(|max| max != "0") {
+        restart.push_str(&format!(", max {}", max));
+        if let Some(period) = option("--respawn-period") {
+            restart.push_str(&format!(" in {}s", period));
+        }
+    }
+    Some(InitService {
+        manager: InitSystem::OpenRc,
+        name: name.clone(),
+        config: format!("{}/{}", INIT_D, name),
+        pidfile: option("--pidfile"),
+        restart,
+    })
+}
+
+// Scripts written before `/run` name pid files under `/var/run`.
+fn mentions(script: &str, pidfile: &str) -> bool {
+    let legacy = pidfile
+        .strip_prefix("/run/")
+        .map(|p| format!("/var/run/{}", p));
+    script.contains(pidfile) || legacy.is_some_and(|legacy| script.contains(&legacy))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::testutil::TempTree;
+
+    #[test]
+    fn test_find_sysv_and_openrc() {
+        let tree = TempTree::new("rc");
+        tree.write("etc/init.d/bind9", "PIDFILE=/var/run/named/named.pid\n")
+            .write("etc/init.d/nginx", "start-stop-daemon --start\n")
+            .write("run/named/named.pid", "3501\n")
+            .write("run/nginx.pid", "812\n")
+            // Only a link to `/run`, whose pid files are listed once.
+            .symlink("../run", "var/run");
+        let sysv = InitScripts::load(&tree.host());
+        tree.write("run/openrc/started/nginx", "").write(
+            "run/openrc/daemons/nginx/001",
+            "exec=/usr/sbin/nginx\npidfile=/run/nginx.pid\n",
+        );
+        let openrc = InitScripts::load(&tree.host());
+
+        assert_eq!(sysv.pidfiles.len(), 2);
+        let named = sysv.find(&[3502, 3501]).unwrap();
+        assert_eq!(named.to_string(), "sysv bind9 (/etc/init.d/bind9)");
+        assert_eq!(named.pidfile.as_deref(), Some("/run/named/named.pid"));
+        assert_eq!(sysv.find(&[812]).map(|s| s.name), Some("nginx".to_string()));
+        assert_eq!(sysv.find(&[4000]), None);
+        // bind9 is not among OpenRC's started services.
+        assert_eq!(openrc.find(&[3501]), None);
+        let nginx = openrc.find(&[813, 812]).unwrap();
+        assert_eq!(nginx.manager, InitSystem::OpenRc);
+        assert_eq!(nginx.restart, "never");
+    }
+
+    #[test]
+    fn test_supervise_daemon() {
+        let cmd: Vec<String> = [
+            "supervise-daemon",
+            "nginx",
+            "--start",
+            "--pidfile",
+            "/run/supervise-nginx.pid",
+            "--respawn-max=10",
+            "--respawn-period",
+            "60",
+            "/usr/sbin/nginx",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        let nginx = supervise_daemon(&cmd).unwrap();
+        assert_eq!(nginx.config, "/etc/init.d/nginx");
+        assert_eq!(nginx.pidfile.as_deref(), Some("/run/supervise-nginx.pid"));
+        assert_eq!(nginx.restart, "respawn, max 10 in 60s");
+    }
+}
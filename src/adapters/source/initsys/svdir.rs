@@ -0,0 +1,79 @@
+use crate::adapters::hostfs::HostFs;
+use crate::core::models::{InitService, InitSystem};
+
+/// The directory a service scanner (`runsvdir`, `s6-svscan`) watches: its
+/// directory argument, else its working directory.
+pub fn scan_dir(cmd: &[String], cwd: Option<&str>) -> Option<String> {
+    cmd.iter()
+        .skip(1)
+        .find(|arg| arg.starts_with('/'))
+        .cloned()
+        .or_else(|| cwd.map(str::to_string))
+}
+
+/// The service that `runsv <dir>` or `s6-supervise <dir>` supervises. The
+/// supervisor runs in the service directory, so its working directory is
+/// the resolved path; failing that, `dir` is relative to the scanner's.
+/// Both restart the service whenever it exits, and a `down` file only keeps
+/// it from starting at boot.
+pub fn service_dir(
+    host: &HostFs,
+    manager: InitSystem,
+    cmd: &[String],
+    cwd: Option<&str>,
+    scan_dir: Option<&str>,
+) -> Option<InitService> {
+    let arg = cmd.get(1)?;
+    let dir = match (cwd, scan_dir) {
+        _ if arg.starts_with('/') => arg.clone(),
+        (Some(cwd), _) => cwd.to_string(),
+        (None, Some(scan)) => format!("{}/{}", scan.trim_end_matches('/'), arg),
+        (None, None) => return None,
+    };
+    let restart = match host.path(&dir).join("down").exists() {
+        true => "always, not started at boot",
+        false => "always",
+    };
+    Some(InitService {
+        manager,
+        name: arg.rsplit('/').next().unwrap_or(arg).to_string(),
+        config: format!("{}/run", dir.trim_end_matches('/')),
+        pidfile: None,
+        restart: restart.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::testutil::TempTree;
+
+    fn argv(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_service_dir() {
+        let tree = TempTree::new("svdir");
+        tree.write("run/service/sshd/down", "");
+        let host = tree.host();
+
+        let scanner = argv(&["s6-svscan", "-c", "500", "/run/service"]);
+        let scan = scan_dir(&scanner, Some("/")).unwrap();
+        let cmd = argv(&["s6-supervise", "sshd"]);
+        let sshd = service_dir(&host, InitSystem::S6, &cmd, None, Some(&scan)).unwrap();
+        let redis = service_dir(
+            &host,
+            InitSystem::Runit,
+            &argv(&["runsv", "redis"]),
+            Some("/etc/sv/redis"),
+            Some("/var/service"),
+        )
+        .unwrap();
+
+        assert_eq!(sshd.to_string(), "s6 sshd (/run/service/sshd/run)");
+        assert_eq!(sshd.restart, "always, not started at boot");
+        assert_eq!(redis.config, "/etc/sv/redis/run");
+        assert_eq!(redis.restart, "always");
+    }
+}
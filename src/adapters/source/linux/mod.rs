@@ -1 +1,2 @@
 pub mod systemd;
+mod trigger;
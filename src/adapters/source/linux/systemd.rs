@@ -1,100 +1,214 @@
-use std::path::Path;
+use super::trigger;
+use crate::core::models::SystemdUnit;
+use chrono::{Local, NaiveDateTime, TimeZone};
+use std::path::PathBuf;
 use std::process::Command;
 
-pub fn get_systemd_service(pid: u32) -> Option<String> {
-    if let Ok(output) = Command::new("systemctl")
-        .arg("status")
-        .arg(pid.to_string())
-        .output()
+const PROPERTIES: &str = "FragmentPath,NRestarts,ActiveEnterTimestamp,Restart,ExecStart,\
+                          TriggeredBy,RequiredBy,WantedBy";
+
+/// The unit owning a process, from the contents of `/proc/<pid>/cgroup`: the
+/// innermost service or scope, or the slice when the process sits directly
+/// in one. Units below `user@<uid>.service` belong to that user's manager.
+pub fn parse_cgroup_unit(content: &str) -> Option<SystemdUnit> {
+    let path = content.lines().find_map(|line| {
+        let mut parts = line.splitn(3, ':');
+        let hierarchy = parts.next()?;
+        let controllers = parts.next()?;
+        let path = parts.next()?;
+        (hierarchy == "0" || controllers == "name=systemd").then_some(path)
+    })?;
+
+    let units: Vec<&str> = path.split('/').filter(|c| !c.is_empty()).collect();
+    let index = match units
+        .iter()
+        .rposition(|u| u.ends_with(".service") || u.ends_with(".scope"))
     {
-        let out_str = String::from_utf8_lossy(&output.stdout);
-        return parse_systemctl_status(&out_str);
+        Some(index) => index,
+        None if units.last()?.ends_with(".slice") => units.len() - 1,
+        None => return None,
+    };
+    let user = units[..index].iter().find_map(|u| {
+        u.strip_prefix("user@")?
+            .strip_suffix(".service")?
+            .parse()
+            .ok()
+    });
+    Some(SystemdUnit {
+        name: units[index].to_string(),
+        user,
+        ..Default::default()
+    })
+}
+
+pub fn parse_cgroup_service(content: &str) -> Option<String> {
+    parse_cgroup_unit(content)
+        .filter(SystemdUnit::is_service)
+        .map(|u| u.name)
+}
+
+/// Reads unit properties through `systemctl show`. The binary can be
+/// swapped for a stub.
+#[derive(Debug, Clone)]
+pub struct Systemctl {
+    binary: PathBuf,
+}
+
+impl Default for Systemctl {
+    fn default() -> Self {
+        Self::new("systemctl")
     }
-    None
 }
 
-fn parse_systemctl_status(output: &str) -> Option<String> {
-    for line in output.lines() {
-        if line.trim().starts_with("Loaded:") && line.contains(".service") {
-            if let Some(start) = line.find("(/") {
-                if let Some(end) = line[start..].find(";") {
-                    let path = &line[start + 1..start + end];
-                    let service_name = Path::new(path)
-                        .file_name()
-                        .map(|f| f.to_string_lossy().into_owned());
-
-                    if let Some(name) = &service_name {
-                        if name.starts_with("user@") {
-                            return None;
-                        }
-                    }
-                    return service_name;
-                }
-            }
+impl Systemctl {
+    pub fn new(binary: impl Into<PathBuf>) -> Self {
+        Self {
+            binary: binary.into(),
         }
     }
-    None
-}
 
-pub fn get_restart_count(service_name: &str) -> Option<u32> {
-    if let Ok(output) = Command::new("systemctl")
-        .args(["show", "-p", "NRestarts", "--value", service_name])
-        .output()
-    {
-        let out_str = String::from_utf8_lossy(&output.stdout);
-        return parse_restart_count(&out_str);
+    /// Fills in `unit`'s properties with one `systemctl show`, asking the
+    /// user's manager for user units, plus one more for the unit that
+    /// triggered it, if any.
+    pub fn load(&self, unit: &mut SystemdUnit) {
+        let user = unit.user;
+        let Some(output) = self.show(&unit.name, user, PROPERTIES) else {
+            return;
+        };
+        apply_properties(unit, &output);
+        unit.trigger = trigger::resolve(&output, |name, properties| {
+            self.show(name, user, properties)
+        });
     }
-    None
-}
 
-fn parse_restart_count(output: &str) -> Option<u32> {
-    output.trim().parse::<u32>().ok()
+    /// `systemctl show -p <properties> -- <unit>`; `None` if it fails.
+    pub fn show(&self, unit: &str, user: Option<u32>, properties: &str) -> Option<String> {
+        let mut command = Command::new(&self.binary);
+        if let Some(uid) = user {
+            command.arg("--user").arg(format!("--machine={}@", uid));
+        }
+        command.args(["show", "-p", properties, "--", unit]);
+        let output = command.output().ok().filter(|o| o.status.success())?;
+        Some(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
 }
 
-pub fn get_fragment_path(service_name: &str) -> Option<String> {
-    if let Ok(output) = Command::new("systemctl")
-        .args(["show", "-p", "FragmentPath", "--value", service_name])
-        .output()
-    {
-        let s = String::from_utf8_lossy(&output.stdout);
-        let path = s.trim();
-        if !path.is_empty() && path != "/dev/null" {
-            return Some(path.to_string());
+fn apply_properties(unit: &mut SystemdUnit, output: &str) {
+    for line in output.lines() {
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = Some(value.trim()).filter(|v| !v.is_empty());
+        match key {
+            "FragmentPath" => {
+                unit.fragment_path = value.filter(|v| *v != "/dev/null").map(str::to_string)
+            }
+            "NRestarts" => unit.restarts = value.and_then(|v| v.parse().ok()),
+            "ActiveEnterTimestamp" => unit.active_since = value.and_then(parse_timestamp),
+            "Restart" => unit.restart_policy = value.map(str::to_string),
+            "ExecStart" => unit.exec_start = value.and_then(parse_exec_start),
+            _ => {}
         }
     }
-    None
+}
+
+/// `Mon 2024-05-06 10:00:00 CEST`, written in systemctl's local zone.
+pub(super) fn parse_timestamp(value: &str) -> Option<u64> {
+    let (rest, zone) = value.rsplit_once(' ')?;
+    let (_, datetime) = rest.split_once(' ')?;
+    let naive = NaiveDateTime::parse_from_str(datetime, "%Y-%m-%d %H:%M:%S").ok()?;
+    let time = match zone {
+        "UTC" => naive.and_utc().timestamp(),
+        _ => Local.from_local_datetime(&naive).earliest()?.timestamp(),
+    };
+    u64::try_from(time).ok()
+}
+
+// `{ path=/usr/sbin/nginx ; argv[]=/usr/sbin/nginx -g daemon on; ; ignore_errors=no ; ... }`:
+// the command line runs up to the next field.
+fn parse_exec_start(value: &str) -> Option<String> {
+    let argv = value.split_once("argv[]=")?.1;
+    let argv = argv.split(" ; ignore_errors=").next()?.trim();
+    (!argv.is_empty()).then(|| argv.to_string())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn unit(content: &str) -> Option<(String, Option<u32>)> {
+        parse_cgroup_unit(content).map(|u| (u.name, u.user))
+    }
+
     #[test]
-    fn test_parse_systemctl_status() {
-        let output = r#"
-● nginx.service - A high performance web server and a reverse proxy server
-   Loaded: loaded (/lib/systemd/system/nginx.service; enabled; vendor preset: enabled)
-   Active: active (running) since Mon 2023-01-16 ...
-"#;
+    fn test_parse_cgroup_unit() {
         assert_eq!(
-            parse_systemctl_status(output),
-            Some("nginx.service".to_string())
+            unit("0::/system.slice/system-getty.slice/getty@tty1.service\n"),
+            Some(("getty@tty1.service".to_string(), None))
         );
+        assert_eq!(
+            unit("0::/user.slice/user-1000.slice/user@1000.service/app.slice/syncthing.service"),
+            Some(("syncthing.service".to_string(), Some(1000)))
+        );
+        assert_eq!(
+            unit("0::/user.slice/user-1000.slice/session-3.scope"),
+            Some(("session-3.scope".to_string(), None))
+        );
+        assert_eq!(
+            unit("0::/user.slice/user-1000.slice/user@1000.service"),
+            Some(("user@1000.service".to_string(), None))
+        );
+        assert_eq!(
+            unit("0::/machine.slice"),
+            Some(("machine.slice".to_string(), None))
+        );
+        assert_eq!(unit("0::/"), None);
+    }
 
-        let invalid = "Loaded: loaded (/etc/init.d/apache2; generated)";
-        assert_eq!(parse_systemctl_status(invalid), None);
-
-        let user_service = r#"
-   Loaded: loaded (/lib/systemd/system/user@.service; static; vendor preset: enabled)
-"#;
-        assert_eq!(parse_systemctl_status(user_service), None);
+    #[test]
+    fn test_parse_cgroup_service() {
+        assert_eq!(
+            parse_cgroup_service("0::/system.slice/nginx.service\n"),
+            Some("nginx.service".to_string())
+        );
+        assert_eq!(
+            parse_cgroup_service("12:pids:/\n1:name=systemd:/system.slice/cron.service"),
+            Some("cron.service".to_string())
+        );
+        assert_eq!(
+            parse_cgroup_service(
+                "0::/user.slice/user-1000.slice/user@1000.service/app.slice/x.service"
+            ),
+            Some("x.service".to_string())
+        );
+        assert_eq!(parse_cgroup_service("0::/init.scope"), None);
     }
 
     #[test]
-    fn test_parse_restart_count() {
-        assert_eq!(parse_restart_count("5\n"), Some(5));
-        assert_eq!(parse_restart_count("0"), Some(0));
-        assert_eq!(parse_restart_count("invalid"), None);
-        assert_eq!(parse_restart_count("  10  "), Some(10));
+    fn test_apply_properties() {
+        let output = "FragmentPath=/lib/systemd/system/nginx.service\nNRestarts=2\n\
+            ActiveEnterTimestamp=Thu 2024-05-02 09:14:28 UTC\nRestart=on-failure\n\
+            ExecStart={ path=/usr/sbin/nginx ; argv[]=/usr/sbin/nginx -g daemon on; master_process on; ; \
+            ignore_errors=no ; start_time=[n/a] ; stop_time=[n/a] ; pid=0 ; code=(null) ; status=0/0 }\n";
+        let mut unit = SystemdUnit::default();
+        apply_properties(&mut unit, output);
+        assert_eq!(
+            unit.fragment_path.as_deref(),
+            Some("/lib/systemd/system/nginx.service")
+        );
+        assert_eq!(unit.restarts, Some(2));
+        assert_eq!(unit.active_since, Some(1714641268));
+        assert_eq!(unit.restart_policy.as_deref(), Some("on-failure"));
+        assert_eq!(
+            unit.exec_start.as_deref(),
+            Some("/usr/sbin/nginx -g daemon on; master_process on;")
+        );
+
+        let mut masked = SystemdUnit::default();
+        apply_properties(
+            &mut masked,
+            "FragmentPath=/dev/null\nActiveEnterTimestamp=\n",
+        );
+        assert_eq!((masked.fragment_path, masked.active_since), (None, None));
     }
 }
@@ -0,0 +1,185 @@
+use super::systemd::parse_timestamp;
+use crate::core::models::{TriggerKind, UnitTrigger};
+
+const TIMER_PROPERTIES: &str =
+    "TimersCalendar,TimersMonotonic,LastTriggerUSec,NextElapseUSecRealtime";
+
+// Calendar specs as systemd normalizes the shorthands it accepts.
+const SHORTHANDS: [(&str, &str); 6] = [
+    ("*-*-* *:*:00", "minutely"),
+    ("*-*-* *:00:00", "hourly"),
+    ("*-*-* 00:00:00", "daily"),
+    ("Mon *-*-* 00:00:00", "weekly"),
+    ("*-*-01 00:00:00", "monthly"),
+    ("*-01-01 00:00:00", "yearly"),
+];
+
+/// The unit that started the one whose `systemctl show` output is
+/// `properties`: a socket, timer or path unit activating it, else the unit
+/// requiring or wanting it. `show` asks for more properties of that unit.
+pub(super) fn resolve(
+    properties: &str,
+    show: impl Fn(&str, &str) -> Option<String>,
+) -> Option<UnitTrigger> {
+    let activator = list(properties, "TriggeredBy")
+        .into_iter()
+        .find_map(|unit| {
+            let kind = match unit.rsplit_once('.')?.1 {
+                "socket" => TriggerKind::Socket,
+                "timer" => TriggerKind::Timer,
+                "path" => TriggerKind::Path,
+                _ => return None,
+            };
+            Some((kind, unit))
+        });
+    let (kind, unit) = activator
+        .or_else(|| first(properties, "RequiredBy").map(|u| (TriggerKind::RequiredBy, u)))
+        .or_else(|| first(properties, "WantedBy").map(|u| (TriggerKind::WantedBy, u)))?;
+
+    let mut trigger = UnitTrigger {
+        kind,
+        unit: unit.to_string(),
+        detail: None,
+        last: None,
+        next: None,
+    };
+    match kind {
+        TriggerKind::Timer => {
+            if let Some(output) = show(unit, TIMER_PROPERTIES) {
+                apply_timer(&mut trigger, &output);
+            }
+        }
+        TriggerKind::Socket => {
+            trigger.detail = show(unit, "Listen").and_then(|o| listen(&o));
+        }
+        TriggerKind::Path => {
+            trigger.detail = show(unit, "Paths").and_then(|o| value(&o, "Paths").map(fields));
+        }
+        TriggerKind::RequiredBy | TriggerKind::WantedBy => {}
+    }
+    Some(trigger)
+}
+
+fn value<'a>(output: &'a str, key: &str) -> Option<&'a str> {
+    output
+        .lines()
+        .find_map(|line| line.strip_prefix(key)?.strip_prefix('='))
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+}
+
+fn list<'a>(output: &'a str, key: &str) -> Vec<&'a str> {
+    value(output, key)
+        .map(|v| v.split_whitespace().collect())
+        .unwrap_or_default()
+}
+
+fn first<'a>(output: &'a str, key: &str) -> Option<&'a str> {
+    list(output, key).into_iter().next()
+}
+
+// `TimersCalendar={ OnCalendar=*-*-* 00:00:00 ; next_elapse=Fri 2024-05-03 00:00:00 UTC }`
+// or `TimersMonotonic={ OnUnitActiveSec=1h ; next_elapse=1h 30min }`: the
+// schedule is the first field.
+fn apply_timer(trigger: &mut UnitTrigger, output: &str) {
+    let schedule = |key| {
+        value(output, key)?
+            .trim_start_matches('{')
+            .split(';')
+            .next()
+    };
+    trigger.detail = schedule("TimersCalendar")
+        .and_then(|s| s.trim().strip_prefix("OnCalendar="))
+        .map(|spec| {
+            SHORTHANDS
+                .iter()
+                .find(|(normalized, _)| *normalized == spec)
+                .map_or(spec, |(_, shorthand)| shorthand)
+                .to_string()
+        })
+        .or_else(|| schedule("TimersMonotonic").map(|s| s.trim().to_string()))
+        .filter(|s| !s.is_empty());
+    trigger.last = value(output, "LastTriggerUSec").and_then(parse_timestamp);
+    trigger.next = value(output, "NextElapseUSecRealtime").and_then(parse_timestamp);
+}
+
+// One `Listen=` line per address, each tagged with its type:
+// `Listen=[::]:80 (Stream)`.
+fn listen(output: &str) -> Option<String> {
+    let addresses: Vec<&str> = output
+        .lines()
+        .filter_map(|line| line.strip_prefix("Listen="))
+        .map(|l| l.rsplit_once(" (").map_or(l, |(address, _)| address).trim())
+        .collect();
+    (!addresses.is_empty()).then(|| addresses.join(", "))
+}
+
+// `{ PathChanged=/etc/app.conf ; PathExists=/run/app.flag }`
+fn fields(value: &str) -> String {
+    value
+        .trim_start_matches('{')
+        .trim_end_matches('}')
+        .split(';')
+        .map(str::trim)
+        .filter(|f| !f.is_empty())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_timer() {
+        let properties = "TriggeredBy=backup.timer\nWantedBy=\n";
+        let trigger = resolve(properties, |unit, props| {
+            assert_eq!((unit, props), ("backup.timer", TIMER_PROPERTIES));
+            Some(
+                "TimersCalendar={ OnCalendar=*-*-* 00:00:00 ; next_elapse=Fri 2024-05-03 00:00:00 UTC }\n\
+                 TimersMonotonic=\nLastTriggerUSec=Thu 2024-05-02 00:00:00 UTC\n\
+                 NextElapseUSecRealtime=Fri 2024-05-03 00:00:00 UTC\n"
+                    .to_string(),
+            )
+        })
+        .unwrap();
+        assert_eq!(trigger.to_string(), "triggered by backup.timer (daily)");
+        assert_eq!(trigger.last, Some(1714608000));
+        assert_eq!(trigger.next, Some(1714694400));
+    }
+
+    #[test]
+    fn test_resolve_socket_and_path() {
+        let socket = resolve("TriggeredBy=sshd.socket\n", |_, _| {
+            Some("Listen=[::]:22 (Stream)\nListen=/run/sshd.sock (Stream)\n".to_string())
+        })
+        .unwrap();
+        assert_eq!(socket.kind, TriggerKind::Socket);
+        assert_eq!(socket.detail.as_deref(), Some("[::]:22, /run/sshd.sock"));
+
+        let path = resolve("TriggeredBy=app.path\n", |_, _| {
+            Some("Paths={ PathChanged=/etc/app.conf ; }\n".to_string())
+        })
+        .unwrap();
+        assert_eq!(
+            path.to_string(),
+            "triggered by app.path (PathChanged=/etc/app.conf)"
+        );
+    }
+
+    #[test]
+    fn test_resolve_dependencies() {
+        let none = |_: &str, _: &str| -> Option<String> { panic!("no follow-up query") };
+        let wanted = "TriggeredBy=\nRequiredBy=\nWantedBy=multi-user.target graphical.target\n";
+        assert_eq!(
+            resolve(wanted, none).unwrap().to_string(),
+            "wanted by multi-user.target"
+        );
+        let required = "RequiredBy=docker.service\nWantedBy=multi-user.target\n";
+        assert_eq!(
+            resolve(required, none).unwrap().kind,
+            TriggerKind::RequiredBy
+        );
+        assert_eq!(resolve("TriggeredBy=\nWantedBy=\n", none), None);
+    }
+}
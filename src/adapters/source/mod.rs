@@ -8,10 +8,13 @@ pub mod cron;
 pub mod detect;
 pub mod git;
 pub mod health;
+pub mod initsys;
 pub mod network;
 pub mod service;
 pub mod shell;
 pub mod supervisor;
+#[cfg(target_os = "linux")]
+mod unixhttp;
 
 #[cfg(target_os = "macos")]
 pub use darwin::*;
@@ -21,6 +24,7 @@ pub use cron::*;
 pub use detect::*;
 pub use git::*;
 pub use health::*;
+pub use initsys::*;
 #[cfg(target_os = "linux")]
 pub use linux::*;
 pub use network::*;
@@ -1,15 +1,23 @@
+use crate::adapters::proc::ProcFs;
+#[cfg(target_os = "linux")]
+use crate::adapters::source::container::read_cgroup;
 #[cfg(target_os = "linux")]
 use crate::adapters::source::linux::systemd;
+pub use crate::adapters::source::linux::systemd::Systemctl;
+use crate::core::models::SystemdUnit;
 
 #[cfg(target_os = "macos")]
 use crate::adapters::source::darwin::launchd;
 
-pub fn get_service_info(pid: u32) -> Option<String> {
+pub fn get_service_info(procfs: &ProcFs, pid: u32) -> Option<String> {
     #[cfg(target_os = "linux")]
     {
-        systemd::get_systemd_service(pid)
+        read_cgroup(procfs, pid).and_then(|c| systemd::parse_cgroup_service(&c))
     }
 
+    #[cfg(not(target_os = "linux"))]
+    let _ = procfs;
+
     #[cfg(target_os = "macos")]
     {
         launchd::get_launchd_service(pid)
@@ -34,25 +42,36 @@ pub fn get_service_info(pid: u32) -> Option<String> {
     }
 }
 
-pub fn get_service_restart_count(service_name: &str) -> Option<u32> {
+pub fn resolve_service(pid: u32, cgroup: Option<&str>) -> Option<String> {
     #[cfg(target_os = "linux")]
     {
-        systemd::get_restart_count(service_name)
+        let _ = pid;
+        cgroup.and_then(systemd::parse_cgroup_service)
     }
 
     #[cfg(not(target_os = "linux"))]
     {
-        let _ = service_name;
-        None
+        let _ = cgroup;
+        get_service_info(&ProcFs::default(), pid)
     }
 }
 
-pub fn get_service_file(service_name: &str) -> Option<String> {
+/// The systemd unit owning a process, from its cgroup; properties are
+/// loaded separately through [`Systemctl`].
+pub fn resolve_unit(cgroup: Option<&str>) -> Option<SystemdUnit> {
     #[cfg(target_os = "linux")]
     {
-        systemd::get_fragment_path(service_name)
+        cgroup.and_then(systemd::parse_cgroup_unit)
     }
 
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = cgroup;
+        None
+    }
+}
+
+pub fn get_service_file(service_name: &str) -> Option<String> {
     #[cfg(target_os = "windows")]
     {
         use crate::adapters::source::windows::service;
@@ -60,7 +79,7 @@ pub fn get_service_file(service_name: &str) -> Option<String> {
         service::get_service_binary_path(clean_name)
     }
 
-    #[cfg(not(any(target_os = "linux", target_os = "windows")))]
+    #[cfg(not(target_os = "windows"))]
     {
         let _ = service_name;
         None
@@ -0,0 +1,278 @@
+use crate::adapters::hostfs::HostFs;
+use crate::core::models::SupervisorProgram;
+use std::collections::HashMap;
+use std::fs;
+
+/// The processes a supervisord config defines, one per `numprocs`, and the
+/// unix socket its XML-RPC interface listens on.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SupervisorConfig {
+    pub programs: Vec<SupervisorProgram>,
+    pub socket: Option<String>,
+}
+
+struct Section {
+    name: String,
+    file: String,
+    line: usize,
+    values: HashMap<String, String>,
+}
+
+impl Section {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.values.get(key).map(String::as_str)
+    }
+
+    // `%(here)s` is the directory of the file the section is in.
+    fn here(&self) -> &str {
+        self.file.rsplit_once('/').map_or("/", |(dir, _)| dir)
+    }
+}
+
+/// Reads `path` and the files its `[include]` section pulls in.
+pub fn load_supervisor_config(host: &HostFs, path: &str) -> SupervisorConfig {
+    let mut sections = read_sections(host, path);
+    let includes: Vec<String> = sections
+        .iter()
+        .filter(|s| s.name == "include")
+        .flat_map(|s| {
+            let here = s.here().to_string();
+            let files = s.get("files").unwrap_or_default().to_string();
+            files
+                .split_whitespace()
+                .flat_map(|pattern| glob(host, &here, pattern))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    for file in includes {
+        sections.extend(read_sections(host, &file));
+    }
+
+    let groups: HashMap<&str, &str> = sections
+        .iter()
+        .filter_map(|s| Some((s.name.strip_prefix("group:")?, s.get("programs")?)))
+        .flat_map(|(group, programs)| programs.split(',').map(move |p| (p.trim(), group)))
+        .collect();
+    let programs = sections
+        .iter()
+        .filter_map(|s| Some((s.name.strip_prefix("program:")?, s)))
+        .flat_map(|(name, section)| expand_program(section, name, groups.get(name).copied()))
+        .collect();
+    let socket = sections
+        .iter()
+        .find(|s| s.name == "unix_http_server")
+        .and_then(|s| Some(expand(s.get("file")?, &[("here", s.here())], 0)))
+        .or_else(|| {
+            let ctl = sections.iter().find(|s| s.name == "supervisorctl")?;
+            let url = ctl.get("serverurl")?.strip_prefix("unix://")?;
+            Some(expand(url, &[("here", ctl.here())], 0))
+        });
+    SupervisorConfig { programs, socket }
+}
+
+// `numprocs` copies of the program, each named by `process_name`.
+fn expand_program(section: &Section, name: &str, group: Option<&str>) -> Vec<SupervisorProgram> {
+    let Some(command) = section.get("command") else {
+        return Vec::new();
+    };
+    let numprocs = section
+        .get("numprocs")
+        .and_then(|n| n.parse().ok())
+        .unwrap_or(1);
+    let start: u32 = section
+        .get("numprocs_start")
+        .and_then(|n| n.parse().ok())
+        .unwrap_or(0);
+    let template = section.get("process_name").unwrap_or("%(program_name)s");
+    let group = group.unwrap_or(name);
+    let vars = [
+        ("program_name", name),
+        ("group_name", group),
+        ("here", section.here()),
+    ];
+    (start..start + numprocs)
+        .map(|num| SupervisorProgram {
+            program: name.to_string(),
+            process: expand(template, &vars, num),
+            group: group.to_string(),
+            config: section.file.clone(),
+            line: section.line,
+            command: expand(command, &vars, num),
+            autorestart: section
+                .get("autorestart")
+                .unwrap_or("unexpected")
+                .to_string(),
+            numprocs,
+            state: None,
+        })
+        .collect()
+}
+
+// Python `%`-formatting as supervisord applies it: `%(name)s`,
+// `%(process_num)02d` and `%%`. Unknown names are left as written.
+fn expand(template: &str, vars: &[(&str, &str)], num: u32) -> String {
+    let mut out = String::new();
+    let mut rest = template;
+    while let Some(at) = rest.find('%') {
+        out.push_str(&rest[..at]);
+        rest = &rest[at..];
+        let Some((name, spec, len)) = directive(rest) else {
+            out.push('%');
+            rest = rest.strip_prefix("%%").unwrap_or(&rest[1..]);
+            continue;
+        };
+        if name == "process_num" {
+            let width = spec.trim_start_matches('0').parse().unwrap_or(0);
+            match spec.starts_with('0') {
+                true => out.push_str(&format!("{:0width$}", num)),
+                false => out.push_str(&format!("{:width$}", num)),
+            }
+        } else if let Some((_, value)) = vars.iter().find(|(n, _)| *n == name) {
+            out.push_str(value);
+        } else {
+            out.push_str(&rest[..len]);
+        }
+        rest = &rest[len..];
+    }
+    out.push_str(rest);
+    out
+}
+
+// `%(name)02d`: the name, the flags and width before the conversion, and
+// the directive's length.
+fn directive(s: &str) -> Option<(&str, &str, usize)> {
+    let (name, r) = s.strip_prefix("%(")?.split_once(')')?;
+    let end = r.find(|c: char| c.is_ascii_alphabetic())?;
+    Some((name, &r[..end], s.len() - r.len() + end + 1))
+}
+
+fn read_sections(host: &HostFs, file: &str) -> Vec<Section> {
+    let Ok(content) = fs::read_to_string(host.path(file)) else {
+        return Vec::new();
+    };
+    let mut sections: Vec<Section> = Vec::new();
+    let mut key: Option<String> = None;
+    for (index, raw) in content.lines().enumerate() {
+        let line = strip_comment(raw);
+        if line.trim().is_empty() || raw.starts_with([';', '#']) {
+            continue;
+        }
+        let section = sections.last_mut();
+        if let Some(name) = line
+            .trim()
+            .strip_prefix('[')
+            .and_then(|l| l.strip_suffix(']'))
+        {
+            sections.push(Section {
+                name: name.trim().to_string(),
+                file: file.to_string(),
+                line: index + 1,
+                values: HashMap::new(),
+            });
+            key = None;
+        } else if raw.starts_with(char::is_whitespace) {
+            // An indented line continues the previous value.
+            if let (Some(section), Some(key)) = (section, &key) {
+                let value = section.values.entry(key.clone()).or_default();
+                value.push('\n');
+                value.push_str(line.trim());
+            }
+        } else if let (Some(section), Some((k, v))) = (section, line.split_once(['=', ':'])) {
+            let k = k.trim().to_ascii_lowercase();
+            section.values.insert(k.clone(), v.trim().to_string());
+            key = Some(k);
+        }
+    }
+    sections
+}
+
+// Inline comments start with `;` after whitespace.
+fn strip_comment(line: &str) -> &str {
+    match line.find(" ;").or_else(|| line.find("\t;")) {
+        Some(at) => &line[..at],
+        None => line,
+    }
+}
+
+// `conf.d/*.conf`, relative to the including file's directory. Only the
+// last component may hold wildcards.
+fn glob(host: &HostFs, here: &str, pattern: &str) -> Vec<String> {
+    let pattern = match pattern.starts_with('/') {
+        true => pattern.to_string(),
+        false => format!("{}/{}", here.trim_end_matches('/'), pattern),
+    };
+    let (dir, name) = pattern.rsplit_once('/').unwrap_or(("", &pattern));
+    if !name.contains(['*', '?']) {
+        return vec![pattern.clone()];
+    }
+    let Ok(entries) = fs::read_dir(host.path(dir)) else {
+        return Vec::new();
+    };
+    let mut files: Vec<String> = entries
+        .flatten()
+        .filter_map(|e| e.file_name().into_string().ok())
+        .filter(|file| wildcard(name.as_bytes(), file.as_bytes()))
+        .map(|file| format!("{}/{}", dir, file))
+        .collect();
+    files.sort();
+    files
+}
+
+fn wildcard(pattern: &[u8], name: &[u8]) -> bool {
+    match (pattern.first(), name.first()) {
+        (None, None) => true,
+        (Some(b'*'), _) => {
+            wildcard(&pattern[1..], name) || !name.is_empty() && wildcard(pattern, &name[1..])
+        }
+        (Some(b'?'), Some(_)) => wildcard(&pattern[1..], &name[1..]),
+        (Some(p), Some(n)) if p == n => wildcard(&pattern[1..], &name[1..]),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::testutil::TempTree;
+
+    #[test]
+    fn test_expand() {
+        let vars = [("program_name", "worker"), ("here", "/etc/supervisor")];
+        assert_eq!(
+            expand("%(program_name)s_%(process_num)02d", &vars, 1),
+            "worker_01"
+        );
+        assert_eq!(
+            expand("%(here)s/run.sh --pct=50%% %(ENV_HOME)s", &vars, 0),
+            "/etc/supervisor/run.sh --pct=50% %(ENV_HOME)s"
+        );
+    }
+
+    #[test]
+    fn test_load_groups() {
+        let tree = TempTree::new("supervisor");
+        let conf = "[unix_http_server]\nfile=%(here)s/supervisor.sock ; comment\n\n\
+            [group:web]\nprograms=app, ws\n\n\
+            [program:app]\ncommand=/srv/app/bin/server\n  --port 8000\n\n\
+            [program:ws]\ncommand=/srv/app/bin/ws\nautorestart=false\n";
+        tree.write("etc/supervisord.conf", conf);
+
+        let config = load_supervisor_config(&tree.host(), "/etc/supervisord.conf");
+        assert_eq!(config.socket.as_deref(), Some("/etc/supervisor.sock"));
+        let names: Vec<String> = config.programs.iter().map(|p| p.name()).collect();
+        assert_eq!(names, ["web:app", "web:ws"]);
+        assert_eq!(
+            config.programs[0].command,
+            "/srv/app/bin/server\n--port 8000"
+        );
+        assert_eq!(config.programs[0].line, 7);
+        assert_eq!(config.programs[1].autorestart, "false");
+    }
+
+    #[test]
+    fn test_wildcard() {
+        assert!(wildcard(b"*.conf", b"worker.conf"));
+        assert!(wildcard(b"app?.ini", b"app1.ini"));
+        assert!(!wildcard(b"*.conf", b"worker.conf.bak"));
+    }
+}
@@ -0,0 +1,185 @@
+mod conf;
+#[cfg(target_os = "linux")]
+mod rpc;
+
+use crate::adapters::hostfs::HostFs;
+use crate::adapters::proc::ProcFs;
+use crate::adapters::source::detect::has_ancestor;
+use crate::core::models::SupervisorProgram;
+use std::path::Path;
+
+pub use conf::{load_supervisor_config, SupervisorConfig};
+
+// Where supervisord looks when it is not given `-c`.
+const DEFAULT_CONFIGS: [&str; 2] = ["/etc/supervisor/supervisord.conf", "/etc/supervisord.conf"];
+
+/// Whether a process is supervisord. Installed from pip it runs as
+/// `python3 /usr/bin/supervisord`, so the command line is checked as well.
+pub fn is_supervisord(name: &str, cmd: &[String]) -> bool {
+    name == "supervisord"
+        || cmd.iter().take(2).any(|arg| {
+            Path::new(arg)
+                .file_name()
+                .is_some_and(|f| f == "supervisord")
+        })
+}
+
+/// Whether supervisord is among `pid`'s ancestors.
+pub fn is_supervisor_process(procfs: &ProcFs, pid: u32) -> bool {
+    has_ancestor(procfs, pid, is_supervisord)
+}
+
+/// The config file supervisord was started with: its `-c` argument, or the
+/// first default location that exists.
+pub fn supervisor_config_path(host: &HostFs, cmd: &[String]) -> Option<String> {
+    let given = cmd
+        .iter()
+        .enumerate()
+        .find_map(|(i, arg)| match arg.as_str() {
+            "-c" | "--configuration" => cmd.get(i + 1).cloned(),
+            arg => arg.strip_prefix("--configuration=").map(str::to_string),
+        });
+    given.or_else(|| {
+        DEFAULT_CONFIGS
+            .iter()
+            .find(|path| host.path(path).is_file())
+            .map(|path| path.to_string())
+    })
+}
+
+/// The program process among `config`'s that a process runs as, given the
+/// command lines of the process and its ancestors below supervisord.
+/// supervisord names the process in its children's environment
+/// (`SUPERVISOR_GROUP_NAME`, `SUPERVISOR_PROCESS_NAME`); without it, the
+/// program's command must match.
+pub fn match_program(
+    config: &SupervisorConfig,
+    commands: &[&[String]],
+    env: &[String],
+) -> Option<SupervisorProgram> {
+    let var = |key: &str| {
+        env.iter()
+            .find_map(|v| v.strip_prefix(key)?.strip_prefix('='))
+    };
+    if let (Some(group), Some(process)) =
+        (var("SUPERVISOR_GROUP_NAME"), var("SUPERVISOR_PROCESS_NAME"))
+    {
+        return config
+            .programs
+            .iter()
+            .find(|p| p.group == group && p.process == process)
+            .cloned();
+    }
+    config
+        .programs
+        .iter()
+        .find(|program| {
+            let words = split_command(&program.command);
+            commands.iter().any(|argv| {
+                same_command(&words, argv) || argv.len() > 1 && same_command(&words, &argv[1..])
+            })
+        })
+        .cloned()
+}
+
+/// Asks supervisord for the process's state over its XML-RPC socket, when
+/// the config names one and it answers.
+pub fn load_supervisor_state(
+    host: &HostFs,
+    config: &SupervisorConfig,
+    program: &mut SupervisorProgram,
+) {
+    #[cfg(target_os = "linux")]
+    if let Some(socket) = &config.socket {
+        program.state = rpc::process_info(&host.path(socket), &program.name()).ok();
+    }
+    #[cfg(not(target_os = "linux"))]
+    let _ = (host, config, program);
+}
+
+// supervisord execs the command split shell-style, finding a bare program
+// name on `PATH`.
+fn same_command(words: &[String], argv: &[String]) -> bool {
+    words.len() == argv.len()
+        && words.iter().zip(argv).enumerate().all(|(i, (word, arg))| {
+            word == arg
+                || i == 0
+                    && !word.contains('/')
+                    && Path::new(arg).file_name() == Some(word.as_ref())
+        })
+}
+
+// Words of `command` as Python's `shlex.split` would produce them.
+fn split_command(command: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut quote = None;
+    let mut chars = command.chars();
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('"'), '\\') | (None, '\\') => {
+                if let Some(next) = chars.next() {
+                    word.get_or_insert_with(String::new).push(next);
+                }
+            }
+            (Some(_), c) => word.get_or_insert_with(String::new).push(c),
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                word.get_or_insert_with(String::new);
+            }
+            (None, c) if c.is_whitespace() => words.extend(word.take()),
+            (None, c) => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    words.extend(word);
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn argv(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_split_command() {
+        assert_eq!(
+            split_command(r#"php artisan queue:work --queue="high,low" 'a b' c\ d"#),
+            argv(&[
+                "php",
+                "artisan",
+                "queue:work",
+                "--queue=high,low",
+                "a b",
+                "c d"
+            ])
+        );
+        assert_eq!(split_command("  ''  x "), argv(&["", "x"]));
+    }
+
+    #[test]
+    fn test_config_path() {
+        let host = HostFs::new("/nonexistent");
+        let cmd = argv(&[
+            "/usr/bin/python3",
+            "/usr/bin/supervisord",
+            "-n",
+            "-c",
+            "/srv/sv.conf",
+        ]);
+        assert!(is_supervisord("python3", &cmd));
+        assert_eq!(
+            supervisor_config_path(&host, &cmd).as_deref(),
+            Some("/srv/sv.conf")
+        );
+        assert_eq!(
+            supervisor_config_path(&host, &argv(&["supervisord", "--configuration=/a.conf"]))
+                .as_deref(),
+            Some("/a.conf")
+        );
+        assert_eq!(supervisor_config_path(&host, &argv(&["supervisord"])), None);
+    }
+}
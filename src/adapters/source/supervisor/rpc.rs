@@ -0,0 +1,155 @@
+use crate::adapters::source::unixhttp;
+use crate::core::models::SupervisorState;
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+
+/// `supervisor.getProcessInfo(name)` over supervisord's XML-RPC interface
+/// on its unix socket.
+pub fn process_info(socket: &Path, name: &str) -> io::Result<SupervisorState> {
+    let call = format!(
+        "<?xml version=\"1.0\"?><methodCall><methodName>supervisor.getProcessInfo</methodName>\
+         <params><param><value><string>{}</string></value></param></params></methodCall>",
+        escape(name)
+    );
+    let (status, body) =
+        unixhttp::request(socket, "POST", "/RPC2", Some(("text/xml", call.as_bytes())))?;
+    if status != 200 {
+        return Err(io::Error::other(format!("supervisord returned {}", status)));
+    }
+    let body = String::from_utf8_lossy(&body);
+    if body.contains("<fault>") {
+        let members = members(&body);
+        let fault = members.get("faultString").map_or("fault", String::as_str);
+        return Err(io::Error::other(format!("supervisord: {}", fault)));
+    }
+    Ok(parse_info(&members(&body)))
+}
+
+fn parse_info(info: &HashMap<String, String>) -> SupervisorState {
+    let number = |key: &str| info.get(key).and_then(|v| v.parse::<i64>().ok());
+    SupervisorState {
+        state: info.get("statename").cloned().unwrap_or_default(),
+        started_at: number("start")
+            .filter(|s| *s > 0)
+            .and_then(|s| u64::try_from(s).ok()),
+        exit_status: number("exitstatus").and_then(|s| i32::try_from(s).ok()),
+        spawn_error: info.get("spawnerr").filter(|e| !e.is_empty()).cloned(),
+    }
+}
+
+// The `<member>`s of the response's struct, scalars only, as text.
+fn members(xml: &str) -> HashMap<String, String> {
+    xml.split("<member>")
+        .skip(1)
+        .filter_map(|member| {
+            let name = between(member, "<name>", "</name>")?;
+            let value = between(member, "<value>", "</value>")?;
+            Some((unescape(name), unescape(scalar(value))))
+        })
+        .collect()
+}
+
+// `<string>RUNNING</string>`, `<int>42</int>`, `<string/>` or bare text.
+fn scalar(value: &str) -> &str {
+    let value = value.trim();
+    if value.starts_with('<') && value.ends_with("/>") {
+        return "";
+    }
+    match value.strip_prefix('<') {
+        Some(tagged) => tagged
+            .split_once('>')
+            .and_then(|(_, rest)| rest.rsplit_once("</"))
+            .map_or("", |(text, _)| text),
+        None => value,
+    }
+}
+
+fn between<'a>(s: &'a str, open: &str, close: &str) -> Option<&'a str> {
+    let start = s.find(open)? + open.len();
+    let end = s[start..].find(close)? + start;
+    Some(&s[start..end])
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn unescape(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::testutil::TempTree;
+    use std::io::{Read, Write};
+    use std::os::unix::net::UnixListener;
+    use std::thread;
+
+    // Answers a single call with `body`, as supervisord would.
+    fn serve(name: &str, body: &'static str) -> (TempTree, std::path::PathBuf) {
+        let tree = TempTree::new(name);
+        let socket = tree.path("api.sock");
+        let listener = UnixListener::bind(&socket).unwrap();
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = [0u8; 2048];
+            let n = stream.read(&mut request).unwrap();
+            let request = String::from_utf8_lossy(&request[..n]);
+            assert!(request.starts_with("POST /RPC2 HTTP/1.1\r\n"));
+            assert!(request.contains("<string>worker:worker_01</string>"));
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: text/xml\r\nContent-Length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+        });
+        (tree, socket)
+    }
+
+    #[test]
+    fn test_process_info_over_socket() {
+        let (_tree, socket) = serve(
+            "supervisor-rpc",
+            "<?xml version='1.0'?><methodResponse><params><param><value><struct>\
+             <member><name>name</name><value><string>worker_01</string></value></member>\
+             <member><name>statename</name><value><string>RUNNING</string></value></member>\
+             <member><name>start</name><value><int>1714641268</int></value></member>\
+             <member><name>exitstatus</name><value><int>1</int></value></member>\
+             <member><name>spawnerr</name><value><string></string></value></member>\
+             </struct></value></param></params></methodResponse>",
+        );
+        let state = process_info(&socket, "worker:worker_01").unwrap();
+        assert_eq!(
+            state,
+            SupervisorState {
+                state: "RUNNING".to_string(),
+                started_at: Some(1714641268),
+                exit_status: Some(1),
+                spawn_error: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_process_info_fault() {
+        let (_tree, socket) = serve(
+            "supervisor-fault",
+            "<?xml version='1.0'?><methodResponse><fault><value><struct>\
+             <member><name>faultCode</name><value><int>10</int></value></member>\
+             <member><name>faultString</name><value><string>BAD_NAME: worker:worker_01</string></value></member>\
+             </struct></value></fault></methodResponse>",
+        );
+        let err = process_info(&socket, "worker:worker_01").unwrap_err();
+        assert_eq!(err.to_string(), "supervisord: BAD_NAME: worker:worker_01");
+    }
+}
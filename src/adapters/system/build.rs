@@ -0,0 +1,270 @@
+use super::{lock, RealSystem};
+use crate::adapters::snapshot::{ProcEntry, SystemSnapshot};
+use crate::adapters::source;
+use crate::core::models::{
+    ContainerInfo, Enrich, PathHolder, PathQuery, Process, SocketInfo, SocketOwner, SystemdUnit,
+};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+pub(super) type GitInfo = (Option<String>, Option<String>);
+
+impl RealSystem {
+    fn get_network_info(
+        snapshot: &SystemSnapshot,
+        pid: u32,
+    ) -> (Vec<u16>, Vec<String>, Vec<String>, Vec<SocketInfo>) {
+        let sockets: Vec<SocketInfo> = snapshot.sockets_for_pid(pid).into_iter().cloned().collect();
+
+        let mut listening: Vec<&SocketInfo> = sockets
+            .iter()
+            .filter(|s| s.is_inet() && s.is_listening())
+            .collect();
+        listening.sort_by(|a, b| (a.port, a.bind_addr()).cmp(&(b.port, b.bind_addr())));
+        listening.dedup_by(|a, b| (a.port, a.bind_addr()) == (b.port, b.bind_addr()));
+
+        // ports, addrs and states stay index-aligned for the output layer.
+        let ports = listening.iter().map(|s| s.port).collect();
+        let addrs = listening
+            .iter()
+            .map(|s| s.bind_addr().to_string())
+            .collect();
+        let states = listening.iter().map(|s| s.state.clone()).collect();
+
+        (ports, addrs, states, sockets)
+    }
+
+    fn git_info(&self, cwd: Option<&String>) -> GitInfo {
+        let Some(cwd) = cwd else {
+            return (None, None);
+        };
+        cached(&self.git_cache, cwd, || {
+            let path = self.host.path(cwd).display().to_string();
+            source::get_git_info(Some(&path))
+        })
+    }
+
+    // Keyed by manager as well as name: each user's manager has its own
+    // units.
+    fn unit_info(&self, unit: SystemdUnit) -> SystemdUnit {
+        let Some(systemctl) = &self.systemctl else {
+            return unit;
+        };
+        let key = format!("{}@{}", unit.name, unit.user.unwrap_or_default());
+        cached(&self.unit_cache, &key, || {
+            let mut unit = unit;
+            systemctl.load(&mut unit);
+            unit
+        })
+    }
+
+    // Processes in one container share a cgroup, and so one engine lookup.
+    fn container_info(&self, snapshot: &SystemSnapshot, pid: u32) -> Option<ContainerInfo> {
+        let cgroup = snapshot.cgroup(pid)?;
+        cached(&self.container_cache, cgroup, || {
+            snapshot.container_info(pid)
+        })
+    }
+
+    pub(super) fn build_process(
+        &self,
+        snapshot: &SystemSnapshot,
+        entry: &ProcEntry,
+        enrich: Enrich,
+    ) -> Process {
+        let pid = entry.pid;
+        let cgroup = if enrich.intersects(Enrich::SERVICE | Enrich::CONTAINER | Enrich::RESTARTS) {
+            snapshot.cgroup(pid)
+        } else {
+            None
+        };
+        let (ports, bind_addrs, port_states, sockets) = if enrich.contains(Enrich::NETWORK) {
+            Self::get_network_info(snapshot, pid)
+        } else {
+            Default::default()
+        };
+        let (git_repo, git_branch) = if enrich.contains(Enrich::GIT) && !self.offline {
+            self.git_info(entry.cwd.as_ref())
+        } else {
+            (None, None)
+        };
+        let service_name = if enrich.intersects(Enrich::SERVICE | Enrich::RESTARTS) {
+            source::resolve_service(pid, cgroup)
+        } else {
+            None
+        };
+        let container_info = if enrich.intersects(Enrich::CONTAINER | Enrich::RESTARTS) {
+            self.container_info(snapshot, pid)
+        } else {
+            cgroup.and_then(source::parse_container)
+        };
+        let container = container_info.as_ref().map(|c| c.runtime.to_string());
+        let health_status = source::get_health_status(
+            pid,
+            entry.status,
+            entry.memory,
+            entry.cpu_usage,
+            entry.start_time,
+        );
+        let forked = source::detect_forked(entry.parent_pid);
+        let cron = if enrich.contains(Enrich::CRON) {
+            self.cron_job(snapshot, entry)
+        } else {
+            None
+        };
+        let supervisor = if enrich.contains(Enrich::SUPERVISOR) {
+            self.supervisor_program(snapshot, entry)
+        } else {
+            None
+        };
+        let init = if enrich.contains(Enrich::INIT) {
+            self.init_service(snapshot, entry)
+        } else {
+            None
+        };
+
+        let unit = if enrich.intersects(Enrich::SERVICE | Enrich::RESTARTS) {
+            source::resolve_unit(cgroup).map(|u| self.unit_info(u))
+        } else {
+            None
+        };
+        let mut final_restart_count = unit
+            .as_ref()
+            .and_then(|u| u.restarts)
+            .filter(|_| enrich.contains(Enrich::RESTARTS));
+        let service_file = unit
+            .as_ref()
+            .and_then(|u| u.fragment_path.clone())
+            .or_else(|| {
+                // Unit files are searched for on the local filesystem.
+                let service = service_name
+                    .as_deref()
+                    .filter(|_| !self.offline && self.host.is_local())?;
+                source::get_service_file(service)
+            })
+            .filter(|_| enrich.contains(Enrich::SERVICE));
+
+        if enrich.contains(Enrich::RESTARTS) && final_restart_count.is_none() {
+            final_restart_count = container_info
+                .as_ref()
+                .and_then(|c| c.state.as_ref())
+                .map(|s| s.restart_count);
+        }
+
+        Process {
+            pid,
+            parent_pid: entry.parent_pid,
+            name: entry.name.clone(),
+            cmd: entry.cmd.clone(),
+            exe_path: entry.exe_path.clone(),
+            uid: entry.uid.clone(),
+            username: entry
+                .uid
+                .as_deref()
+                .and_then(|uid| snapshot.username(uid))
+                .map(str::to_string),
+            start_time: entry.start_time,
+            cwd: entry.cwd.clone(),
+            git_repo,
+            git_branch,
+            container,
+            container_info: container_info.filter(|_| enrich.contains(Enrich::CONTAINER)),
+            service: service_name,
+            ports,
+            bind_addrs,
+            port_states,
+            sockets,
+            restart_count: final_restart_count,
+            service_file,
+            unit: unit.filter(|_| enrich.contains(Enrich::SERVICE)),
+            cron,
+            supervisor,
+            init,
+            health: health_status,
+            forked,
+            env: if enrich.contains(Enrich::ENV) {
+                entry.env.clone()
+            } else {
+                Vec::new()
+            },
+            cpu_usage: entry.cpu_usage,
+            memory_usage: entry.memory,
+            files: if enrich.contains(Enrich::FILES) {
+                snapshot.file_context(pid)
+            } else {
+                None
+            },
+            memory: if enrich.contains(Enrich::MEMORY) {
+                snapshot.memory_context(pid)
+            } else {
+                None
+            },
+            resources: if enrich.contains(Enrich::RESOURCES) {
+                snapshot.resource_context(pid)
+            } else {
+                None
+            },
+            cgroup: if enrich.contains(Enrich::RESOURCES) {
+                snapshot.cgroup_info(pid)
+            } else {
+                None
+            },
+        }
+    }
+
+    /// Each process holding a socket that satisfies `matches`, paired with
+    /// those sockets.
+    pub(super) fn socket_owners(
+        &self,
+        matches: impl Fn(&SocketInfo) -> bool,
+        enrich: Enrich,
+    ) -> Vec<SocketOwner> {
+        let snapshot = self.snapshot();
+        snapshot
+            .find_by_socket(&matches)
+            .into_iter()
+            .map(|entry| SocketOwner {
+                process: self.build_process(&snapshot, entry, enrich),
+                sockets: snapshot
+                    .sockets_for_pid(entry.pid)
+                    .into_iter()
+                    .filter(|s| matches(s))
+                    .cloned()
+                    .collect(),
+            })
+            .collect()
+    }
+
+    /// Each process keeping a path that matches `query` in use, paired with
+    /// the ways it holds those paths.
+    pub(super) fn path_holders(&self, query: &PathQuery, enrich: Enrich) -> Vec<PathHolder> {
+        let snapshot = self.snapshot();
+        snapshot
+            .pids()
+            .into_iter()
+            .filter_map(|pid| {
+                let mut paths = snapshot.held_paths(pid);
+                paths.retain(|held| query.matches(&held.path));
+                let entry = snapshot.process(pid).filter(|_| !paths.is_empty())?;
+                Some(PathHolder {
+                    process: self.build_process(&snapshot, entry, enrich),
+                    paths,
+                })
+            })
+            .collect()
+    }
+}
+
+// The lock is not held while `load` runs, so slow lookups for different keys
+// can proceed in parallel.
+pub(super) fn cached<V: Clone>(
+    cache: &Mutex<HashMap<String, V>>,
+    key: &str,
+    load: impl FnOnce() -> V,
+) -> V {
+    if let Some(hit) = lock(cache).get(key) {
+        return hit.clone();
+    }
+    let value = load();
+    lock(cache).entry(key.to_string()).or_insert(value).clone()
+}
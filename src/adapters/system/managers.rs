@@ -0,0 +1,96 @@
+use super::build::cached;
+use super::{lock, RealSystem};
+use crate::adapters::snapshot::{ProcEntry, SystemSnapshot};
+use crate::adapters::source;
+use crate::core::models::{CronJob, InitService, InitSystem, SupervisorProgram};
+
+impl RealSystem {
+    // Crontabs are read once per refresh, the first time a process below a
+    // cron daemon is built.
+    pub(super) fn cron_job(&self, snapshot: &SystemSnapshot, entry: &ProcEntry) -> Option<CronJob> {
+        let (chain, _) = below(snapshot, entry, |p| source::is_cron_daemon(&p.name))?;
+        let mut jobs = lock(&self.cron_jobs);
+        let jobs = jobs.get_or_insert_with(|| source::load_cron_jobs(&self.host));
+        let user = entry.uid.as_deref().and_then(|uid| snapshot.username(uid));
+        source::match_job(jobs, &commands(&chain), user)
+    }
+
+    // Each supervisord's config is read once, keyed by its path.
+    pub(super) fn supervisor_program(
+        &self,
+        snapshot: &SystemSnapshot,
+        entry: &ProcEntry,
+    ) -> Option<SupervisorProgram> {
+        let (chain, daemon) = below(snapshot, entry, |p| source::is_supervisord(&p.name, &p.cmd))?;
+        let path = source::supervisor_config_path(&self.host, &daemon.cmd)?;
+        let config = cached(&self.supervisor_cache, &path, || {
+            source::load_supervisor_config(&self.host, &path)
+        });
+        let mut program = source::match_program(&config, &commands(&chain), &entry.env)?;
+        if !self.offline {
+            source::load_supervisor_state(&self.host, &config, &mut program);
+        }
+        Some(program)
+    }
+
+    // Service directories and PM2 apps are found through the process that
+    // supervises them, init scripts through the pid files their daemons
+    // leave under `/run`, which are read once per refresh. A pid file names
+    // the process itself or, for a daemon's workers, its parent; anything
+    // further down, such as a login shell under sshd, is not the service.
+    pub(super) fn init_service(
+        &self,
+        snapshot: &SystemSnapshot,
+        entry: &ProcEntry,
+    ) -> Option<InitService> {
+        let supervisor = |p: &ProcEntry| source::service_supervisor(&p.name);
+        if let Some((_, daemon)) = below(snapshot, entry, |p| supervisor(p).is_some()) {
+            return match supervisor(daemon)? {
+                InitSystem::OpenRc => source::supervise_daemon(&daemon.cmd),
+                manager => {
+                    let scanner = daemon.parent_pid.and_then(|pid| snapshot.process(pid));
+                    let scan_dir = scanner.and_then(|s| source::scan_dir(&s.cmd, s.cwd.as_deref()));
+                    let cwd = daemon.cwd.as_deref();
+                    source::service_dir(&self.host, manager, &daemon.cmd, cwd, scan_dir.as_deref())
+                }
+            };
+        }
+        if let Some((chain, daemon)) = below(snapshot, entry, |p| source::is_pm2_daemon(&p.cmd)) {
+            let home = source::pm2_home(&daemon.cmd, &entry.env)?;
+            return source::pm2_app(&self.host, &home, chain.last()?.pid);
+        }
+        let pids: Vec<u32> =
+            std::iter::successors(Some(entry), |p| snapshot.process(p.parent_pid?))
+                .take_while(|p| p.pid > 1)
+                .take(2)
+                .map(|p| p.pid)
+                .collect();
+        lock(&self.init_scripts)
+            .get_or_insert_with(|| source::InitScripts::load(&self.host))
+            .find(&pids)
+    }
+}
+
+// `entry` and its ancestors up to the first one that `is_manager` picks out,
+// and that manager; `None` if `entry` is the manager or is not below one.
+fn below<'a>(
+    snapshot: &'a SystemSnapshot,
+    entry: &'a ProcEntry,
+    is_manager: impl Fn(&ProcEntry) -> bool,
+) -> Option<(Vec<&'a ProcEntry>, &'a ProcEntry)> {
+    let mut chain = Vec::new();
+    let mut current = entry;
+    // In a container the manager is often PID 1 itself.
+    while !is_manager(current) {
+        if current.pid <= 1 {
+            return None;
+        }
+        chain.push(current);
+        current = snapshot.process(current.parent_pid?)?;
+    }
+    (!chain.is_empty()).then_some((chain, current))
+}
+
+fn commands<'a>(chain: &[&'a ProcEntry]) -> Vec<&'a [String]> {
+    chain.iter().map(|p| p.cmd.as_slice()).collect()
+}
@@ -0,0 +1,209 @@
+mod build;
+mod managers;
+
+use crate::adapters::hostfs::HostFs;
+use crate::adapters::proc::ProcFs;
+use crate::adapters::snapshot::SystemSnapshot;
+use crate::adapters::source::{InitScripts, SupervisorConfig, Systemctl};
+use crate::core::models::{
+    ContainerInfo, CronJob, Enrich, PathHolder, PathQuery, Process, RemoteFilter, SocketOwner,
+    SystemdUnit,
+};
+use crate::core::ports::{SystemError, SystemProvider};
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard, PoisonError, RwLock, RwLockReadGuard};
+use sysinfo::System;
+
+use build::GitInfo;
+
+#[derive(Default)]
+pub struct RealSystem {
+    sys: Mutex<System>,
+    procfs: ProcFs,
+    host: HostFs,
+    snapshot: RwLock<SystemSnapshot>,
+    git_cache: Mutex<HashMap<String, GitInfo>>,
+    unit_cache: Mutex<HashMap<String, SystemdUnit>>,
+    container_cache: Mutex<HashMap<String, Option<ContainerInfo>>>,
+    cron_jobs: Mutex<Option<Vec<CronJob>>>,
+    supervisor_cache: Mutex<HashMap<String, SupervisorConfig>>,
+    init_scripts: Mutex<Option<InitScripts>>,
+    systemctl: Option<Systemctl>,
+    offline: bool,
+}
+
+impl RealSystem {
+    pub fn new() -> Self {
+        Self::with_roots(ProcFs::default(), HostFs::default())
+    }
+
+    /// Inspects a host whose procfs and root filesystem are mounted
+    /// elsewhere. On Linux, a procfs root other than `/proc` is parsed
+    /// directly, since sysinfo can only read the live one. The local
+    /// `systemctl` only describes a host at `/`.
+    pub fn with_roots(procfs: ProcFs, host: HostFs) -> Self {
+        let sys = if Self::reads_procfs(&procfs) {
+            System::new()
+        } else {
+            System::new_all()
+        };
+        let mut system = Self {
+            sys: Mutex::new(sys),
+            procfs,
+            systemctl: host.is_local().then(Systemctl::default),
+            host,
+            ..Default::default()
+        };
+        system.snapshot = RwLock::new(system.take_snapshot());
+        system
+    }
+
+    /// Serves a fixed snapshot. Nothing outside it is consulted: no
+    /// subprocesses, sockets, git lookups or refreshes. Host files, such as
+    /// crontabs, are read under the snapshot's host root.
+    pub fn offline(snapshot: SystemSnapshot) -> Self {
+        Self {
+            host: snapshot.host().clone(),
+            snapshot: RwLock::new(snapshot),
+            offline: true,
+            ..Default::default()
+        }
+    }
+
+    /// Reads systemd unit properties through `systemctl` rather than the
+    /// one on `PATH`.
+    pub fn with_systemctl(mut self, systemctl: Systemctl) -> Self {
+        self.systemctl = Some(systemctl);
+        self
+    }
+
+    fn snapshot(&self) -> RwLockReadGuard<'_, SystemSnapshot> {
+        self.snapshot.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn reads_procfs(procfs: &ProcFs) -> bool {
+        cfg!(target_os = "linux") && *procfs != ProcFs::default()
+    }
+
+    fn take_snapshot(&self) -> SystemSnapshot {
+        #[cfg(target_os = "linux")]
+        if Self::reads_procfs(&self.procfs) {
+            return SystemSnapshot::from_procfs(self.procfs.clone()).with_host(self.host.clone());
+        }
+        let mut sys = lock(&self.sys);
+        sys.refresh_all();
+        SystemSnapshot::capture(&sys).with_host(self.host.clone())
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+impl SystemProvider for RealSystem {
+    fn get_process_by_pid(&self, pid: u32, enrich: Enrich) -> Result<Process, SystemError> {
+        let snapshot = self.snapshot();
+        let entry = snapshot
+            .process(pid)
+            .ok_or_else(|| SystemError::ProcessNotFound(format!("PID {} not found", pid)))?;
+        Ok(self.build_process(&snapshot, entry, enrich))
+    }
+
+    fn find_processes_by_name(
+        &self,
+        name_query: &str,
+        enrich: Enrich,
+    ) -> Result<Vec<Process>, SystemError> {
+        let snapshot = self.snapshot();
+        let results: Vec<Process> = snapshot
+            .find_by_name(name_query)
+            .into_iter()
+            .map(|entry| self.build_process(&snapshot, entry, enrich))
+            .collect();
+
+        if results.is_empty() {
+            Err(SystemError::ProcessNotFound(format!(
+                "No processes matching '{}'",
+                name_query
+            )))
+        } else {
+            Ok(results)
+        }
+    }
+
+    fn find_processes_by_port(
+        &self,
+        port: u16,
+        enrich: Enrich,
+    ) -> Result<Vec<SocketOwner>, SystemError> {
+        let owners = self.socket_owners(|s| s.is_inet() && s.port == port, enrich);
+        if owners.is_empty() {
+            Err(SystemError::ProcessNotFound(format!(
+                "No process found on port {}",
+                port
+            )))
+        } else {
+            Ok(owners)
+        }
+    }
+
+    fn find_processes_by_remote(
+        &self,
+        remote: &RemoteFilter,
+        enrich: Enrich,
+    ) -> Result<Vec<SocketOwner>, SystemError> {
+        let owners = self.socket_owners(
+            |s| s.is_inet() && s.state == "ESTABLISHED" && remote.matches(&s.remote_addr),
+            enrich,
+        );
+        if owners.is_empty() {
+            Err(SystemError::ProcessNotFound(format!(
+                "No process connected to {}",
+                remote
+            )))
+        } else {
+            Ok(owners)
+        }
+    }
+
+    fn find_processes_by_path(
+        &self,
+        query: &PathQuery,
+        enrich: Enrich,
+    ) -> Result<Vec<PathHolder>, SystemError> {
+        let holders = self.path_holders(query, enrich);
+        if holders.is_empty() {
+            Err(SystemError::ProcessNotFound(format!(
+                "No process holds {}",
+                query
+            )))
+        } else {
+            Ok(holders)
+        }
+    }
+
+    fn get_all_pids(&self) -> Result<Vec<u32>, SystemError> {
+        Ok(self.snapshot().pids())
+    }
+
+    fn refresh(&self) -> Result<(), SystemError> {
+        if self.offline {
+            return Ok(());
+        }
+        let snapshot = self.take_snapshot();
+        // Engine API state, unit properties such as NRestarts, git branches,
+        // crontabs, supervisord's config and pid files all change between
+        // refreshes.
+        lock(&self.container_cache).clear();
+        lock(&self.unit_cache).clear();
+        lock(&self.git_cache).clear();
+        lock(&self.cron_jobs).take();
+        lock(&self.supervisor_cache).clear();
+        lock(&self.init_scripts).take();
+        *self
+            .snapshot
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = snapshot;
+        Ok(())
+    }
+}
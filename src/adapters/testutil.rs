@@ -0,0 +1,59 @@
+use crate::adapters::hostfs::HostFs;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A scratch host tree for tests, removed when dropped, so a failing
+/// assertion does not leave it behind.
+pub struct TempTree {
+    root: PathBuf,
+}
+
+impl TempTree {
+    /// `name` keeps the trees of tests running in parallel apart.
+    pub fn new(name: &str) -> Self {
+        let root = std::env::temp_dir().join(format!("witr-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        Self { root }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn host(&self) -> HostFs {
+        HostFs::new(&self.root)
+    }
+
+    pub fn path(&self, rel: &str) -> PathBuf {
+        self.root.join(rel)
+    }
+
+    /// Writes `content` to `rel`, creating the directories above it.
+    pub fn write(&self, rel: &str, content: impl AsRef<[u8]>) -> &Self {
+        let path = self.path(rel);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+        self
+    }
+
+    pub fn mkdir(&self, rel: &str) -> &Self {
+        fs::create_dir_all(self.path(rel)).unwrap();
+        self
+    }
+
+    /// Links `rel` to `target`, creating the directories above it.
+    #[cfg(unix)]
+    pub fn symlink(&self, target: &str, rel: &str) -> &Self {
+        let path = self.path(rel);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::os::unix::fs::symlink(target, path).unwrap();
+        self
+    }
+}
+
+impl Drop for TempTree {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.root);
+    }
+}
@@ -0,0 +1,150 @@
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+use witr_rs::adapters::hostfs::HostFs;
+use witr_rs::adapters::proc::ProcFs;
+use witr_rs::core::models::{Enrich, PathQuery, RemoteFilter, Thresholds};
+
+#[derive(Parser, Debug)]
+#[command(name = "witr-rs", version, about = "Why is this running? (Rust edition)", long_about = None)]
+pub struct Args {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    pub name: Option<String>,
+
+    #[arg(short = 'p', long)]
+    pub pid: Option<u32>,
+
+    #[arg(short = 'P', long)]
+    pub port: Option<u16>,
+
+    #[arg(
+        long,
+        value_parser = RemoteFilter::parse,
+        help = "Find processes connected to addr[:port] or a CIDR block"
+    )]
+    pub remote: Option<RemoteFilter>,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Find processes holding this file open, as cwd, exe or mapped library"
+    )]
+    pub file: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_name = "DIR",
+        conflicts_with = "file",
+        help = "Find processes keeping anything under this mountpoint in use"
+    )]
+    pub mount: Option<PathBuf>,
+
+    #[arg(long)]
+    pub short: bool,
+
+    #[arg(long)]
+    pub tree: bool,
+
+    #[arg(long)]
+    pub json: bool,
+
+    #[arg(
+        long,
+        value_parser = Enrich::from_fields,
+        help = "Only look up these fields (ports,git,service,container,restarts,env,files,memory,resources,cron,supervisor,init)"
+    )]
+    pub fields: Option<Enrich>,
+
+    #[arg(long)]
+    pub warnings: bool,
+
+    #[arg(
+        long,
+        value_name = "PERCENT",
+        default_value = "80",
+        value_parser = Thresholds::parse_percent,
+        help = "Warn when this share of the open-files limit is in use"
+    )]
+    pub fd_threshold: f64,
+
+    #[arg(long)]
+    pub no_color: bool,
+
+    #[arg(long)]
+    pub env: bool,
+
+    #[arg(long, help = "List open files, locks and inotify watches")]
+    pub files: bool,
+
+    #[arg(long, help = "Show memory usage and mapped libraries")]
+    pub maps: bool,
+
+    #[arg(long, aliases = ["sec", "scan"])]
+    pub security_scan: bool,
+
+    #[arg(long, help = "Live watch mode")]
+    pub watch: bool,
+
+    #[arg(
+        long,
+        help = "Read processes from a captured /proc tree instead of the live host"
+    )]
+    pub fixture: Option<PathBuf>,
+
+    #[arg(long, help = "Read procfs from this directory instead of /proc")]
+    pub proc_root: Option<PathBuf>,
+
+    #[arg(long, help = "Read host files such as /etc/passwd under this root")]
+    pub host_root: Option<PathBuf>,
+}
+
+impl Args {
+    pub fn thresholds(&self) -> Thresholds {
+        Thresholds {
+            fd_usage: self.fd_threshold,
+        }
+    }
+
+    /// `--file` or `--mount` as an absolute path. Symlinks are only resolved
+    /// on the live host; elsewhere they would point into this machine.
+    pub fn path_query(&self) -> Option<PathQuery> {
+        let live = self.fixture.is_none() && self.host_root.is_none();
+        let resolve = |path: &PathBuf| {
+            live.then(|| std::fs::canonicalize(path).ok())
+                .flatten()
+                .or_else(|| std::path::absolute(path).ok())
+                .unwrap_or_else(|| path.clone())
+        };
+        match (&self.file, &self.mount) {
+            (Some(file), _) => Some(PathQuery::File(resolve(file))),
+            (None, Some(dir)) => Some(PathQuery::Under(resolve(dir))),
+            (None, None) => None,
+        }
+    }
+
+    // A bare --host-root implies the host's procfs is mounted beneath it.
+    pub fn roots(&self) -> (ProcFs, HostFs) {
+        let host = self.host_root.clone().map(HostFs::new).unwrap_or_default();
+        let procfs = match &self.proc_root {
+            Some(root) => ProcFs::new(root),
+            None => host.proc_fs(),
+        };
+        (procfs, host)
+    }
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Record /proc into a tar archive that --fixture can replay once unpacked
+    Capture {
+        #[arg(short, long)]
+        output: PathBuf,
+
+        #[arg(
+            long,
+            help = "Also record process environments, which may contain secrets"
+        )]
+        with_env: bool,
+    },
+}
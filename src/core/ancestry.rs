@@ -4,6 +4,16 @@ pub fn build_ancestry_tree(processes: Vec<Process>) -> Vec<Process> {
     processes.into_iter().rev().collect()
 }
 
+/// The nearest process present in every chain, each ordered root first as
+/// `build_ancestry_tree` returns them. May be one of the chains' targets.
+pub fn shared_ancestor(chains: &[Vec<Process>]) -> Option<&Process> {
+    let (first, rest) = chains.split_first()?;
+    first.iter().rev().find(|p| {
+        rest.iter()
+            .all(|chain| chain.iter().any(|q| q.pid == p.pid))
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -19,4 +29,25 @@ mod tests {
         let tree = build_ancestry_tree(list);
         assert_eq!(tree.len(), 2);
     }
+
+    #[test]
+    fn test_shared_ancestor() {
+        let chain = |pids: &[u32]| -> Vec<Process> {
+            pids.iter()
+                .map(|&pid| Process {
+                    pid,
+                    ..Default::default()
+                })
+                .collect()
+        };
+        let master = chain(&[1, 812]);
+        let worker = chain(&[1, 812, 813]);
+        let other = chain(&[1, 900]);
+
+        let shared = |chains: &[Vec<Process>]| shared_ancestor(chains).map(|p| p.pid);
+        assert_eq!(shared(&[master.clone(), worker.clone()]), Some(812));
+        assert_eq!(shared(&[worker, other]), Some(1));
+        assert_eq!(shared(&[master, chain(&[5])]), None);
+        assert_eq!(shared(&[]), None);
+    }
 }
@@ -0,0 +1,203 @@
+use crate::core::models::{Enrich, InspectionResult, Process, Thresholds};
+use crate::core::ports::{SystemError, SystemProvider};
+use std::collections::{HashMap, HashSet};
+use std::panic;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
+use std::thread;
+
+type Slot = Arc<OnceLock<Option<Process>>>;
+
+/// Inspects many PIDs at once. Lookups are shared through a pid → Process
+/// cache, so common ancestors such as init are only fetched once per run.
+pub struct InspectionEngine<'a, S: SystemProvider> {
+    sys: &'a S,
+    enrich: Enrich,
+    thresholds: Thresholds,
+    cache: Mutex<HashMap<u32, Slot>>,
+}
+
+impl<'a, S: SystemProvider> InspectionEngine<'a, S> {
+    pub fn new(sys: &'a S, enrich: Enrich) -> Self {
+        Self {
+            sys,
+            enrich,
+            thresholds: Thresholds::default(),
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn with_thresholds(mut self, thresholds: Thresholds) -> Self {
+        self.thresholds = thresholds;
+        self
+    }
+
+    // The map lock is only held to find the slot; concurrent walks that
+    // reach the same PID wait on its slot instead of fetching it twice.
+    fn fetch(&self, pid: u32) -> Option<Process> {
+        let slot = self
+            .cache
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(pid)
+            .or_default()
+            .clone();
+        slot.get_or_init(|| self.sys.get_process_by_pid(pid, self.enrich).ok())
+            .clone()
+    }
+
+    pub fn ancestry(&self, pid: u32) -> Vec<Process> {
+        let mut chain = Vec::new();
+        let mut current_pid = Some(pid);
+        let mut loop_detector = HashSet::new();
+
+        while let Some(pid) = current_pid {
+            if !loop_detector.insert(pid) {
+                break;
+            }
+            match self.fetch(pid) {
+                Some(p) => {
+                    current_pid = p.parent_pid;
+                    chain.push(p);
+                }
+                None => break,
+            }
+        }
+
+        crate::core::ancestry::build_ancestry_tree(chain)
+    }
+
+    pub fn inspect(&self, pid: u32) -> Result<InspectionResult, SystemError> {
+        let process = self
+            .fetch(pid)
+            .ok_or_else(|| SystemError::ProcessNotFound(format!("PID {} not found", pid)))?;
+        let ancestry = self.ancestry(pid);
+        Ok(InspectionResult::with_thresholds(
+            process,
+            ancestry,
+            &self.thresholds,
+        ))
+    }
+
+    /// Inspects every PID on a pool of worker threads. The output keeps the
+    /// order of `pids`; PIDs that vanished mid-scan are skipped.
+    pub fn inspect_many(&self, pids: &[u32]) -> Vec<InspectionResult> {
+        self.par_map(pids, |pid| self.inspect(pid).ok())
+    }
+
+    /// Fetches every PID without walking ancestry, in the order of `pids`.
+    pub fn processes(&self, pids: &[u32]) -> Vec<Process> {
+        self.par_map(pids, |pid| self.fetch(pid))
+    }
+
+    fn par_map<T: Send>(&self, pids: &[u32], f: impl Fn(u32) -> Option<T> + Sync) -> Vec<T> {
+        let workers = thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1)
+            .min(pids.len().max(1));
+        let next = AtomicUsize::new(0);
+
+        let mut indexed: Vec<(usize, T)> = thread::scope(|scope| {
+            let handles: Vec<_> = (0..workers)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut local = Vec::new();
+                        loop {
+                            let i = next.fetch_add(1, Ordering::Relaxed);
+                            let Some(&pid) = pids.get(i) else {
+                                break;
+                            };
+                            if let Some(item) = f(pid) {
+                                local.push((i, item));
+                            }
+                        }
+                        local
+                    })
+                })
+                .collect();
+            // A worker's panic would otherwise leave its share of `pids`
+            // silently missing from the output.
+            handles
+                .into_iter()
+                .flat_map(|h| h.join().unwrap_or_else(|e| panic::resume_unwind(e)))
+                .collect()
+        });
+
+        indexed.sort_by_key(|(i, _)| *i);
+        indexed.into_iter().map(|(_, item)| item).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ports::MockSystemProvider;
+
+    fn process(pid: u32, parent_pid: Option<u32>) -> Process {
+        Process {
+            pid,
+            parent_pid,
+            name: format!("proc{}", pid),
+            health: "healthy".into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_ancestors_are_fetched_once() {
+        let mut mock = MockSystemProvider::new();
+        mock.expect_get_process_by_pid()
+            .with(mockall::predicate::eq(1), mockall::predicate::always())
+            .times(1)
+            .returning(|pid, _| Ok(process(pid, None)));
+        mock.expect_get_process_by_pid()
+            .with(mockall::predicate::eq(10), mockall::predicate::always())
+            .times(1)
+            .returning(|pid, _| Ok(process(pid, Some(1))));
+        mock.expect_get_process_by_pid()
+            .with(
+                mockall::predicate::in_iter(100..110),
+                mockall::predicate::always(),
+            )
+            .times(10)
+            .returning(|pid, _| Ok(process(pid, Some(10))));
+
+        let engine = InspectionEngine::new(&mock, Enrich::empty());
+        let pids: Vec<u32> = (100..110).collect();
+        let results = engine.inspect_many(&pids);
+
+        assert_eq!(results.len(), 10);
+        for (pid, res) in pids.iter().zip(&results) {
+            assert_eq!(res.process.pid, *pid);
+            assert_eq!(res.ancestry.len(), 3);
+            assert_eq!(res.ancestry.last().unwrap().pid, *pid);
+        }
+    }
+
+    #[test]
+    fn test_missing_pids_are_skipped() {
+        let mut mock = MockSystemProvider::new();
+        mock.expect_get_process_by_pid()
+            .with(mockall::predicate::eq(5), mockall::predicate::always())
+            .returning(|pid, _| Ok(process(pid, None)));
+        mock.expect_get_process_by_pid()
+            .with(mockall::predicate::eq(6), mockall::predicate::always())
+            .returning(|_, _| Err(SystemError::ProcessNotFound("6".into())));
+
+        let engine = InspectionEngine::new(&mock, Enrich::empty());
+        let results = engine.inspect_many(&[6, 5]);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].process.pid, 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "provider bug")]
+    fn test_worker_panics_propagate() {
+        let mut mock = MockSystemProvider::new();
+        mock.expect_get_process_by_pid()
+            .returning(|_, _| panic!("provider bug"));
+
+        let engine = InspectionEngine::new(&mock, Enrich::empty());
+        engine.processes(&[1, 2, 3]);
+    }
+}
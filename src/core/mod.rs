@@ -1,5 +1,6 @@
 pub mod ancestry;
 pub mod color;
+pub mod engine;
 pub mod models;
 pub mod ports;
 pub mod service;
@@ -0,0 +1,129 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Where a process sits in the cgroup tree, outermost level first, with the
+/// limits set at each level.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CgroupInfo {
+    /// 1 for a legacy hierarchy, 2 for the unified one.
+    pub version: u8,
+    pub path: String,
+    pub levels: Vec<CgroupLevel>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CgroupLevel {
+    pub name: String,
+    pub kind: CgroupKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_max: Option<u64>,
+    /// CPU quota in CPUs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu_max: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pids_max: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CgroupKind {
+    Slice,
+    Service,
+    /// A logind user session, `session-<id>.scope`.
+    Session,
+    /// A container runtime's scope, e.g. `docker-<id>.scope`.
+    Container,
+    Scope,
+    Other,
+}
+
+const CONTAINER_SCOPES: &[&str] = &["docker-", "libpod-", "cri-containerd-", "crio-"];
+
+impl CgroupKind {
+    /// Classifies a cgroup directory by systemd's unit naming.
+    pub fn from_name(name: &str) -> Self {
+        if name.ends_with(".slice") {
+            CgroupKind::Slice
+        } else if name.ends_with(".service") {
+            CgroupKind::Service
+        } else if let Some(scope) = name.strip_suffix(".scope") {
+            if scope.starts_with("session-") {
+                CgroupKind::Session
+            } else if CONTAINER_SCOPES.iter().any(|p| scope.starts_with(p)) {
+                CgroupKind::Container
+            } else {
+                CgroupKind::Scope
+            }
+        } else {
+            CgroupKind::Other
+        }
+    }
+}
+
+impl fmt::Display for CgroupKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            CgroupKind::Slice => "slice",
+            CgroupKind::Service => "service",
+            CgroupKind::Session => "user session",
+            CgroupKind::Container => "container",
+            CgroupKind::Scope => "scope",
+            CgroupKind::Other => "cgroup",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl CgroupInfo {
+    /// The innermost unit, session or container scope, which owns the
+    /// process whether or not it started it directly.
+    pub fn owner(&self) -> Option<&CgroupLevel> {
+        self.levels
+            .iter()
+            .rev()
+            .find(|l| !matches!(l.kind, CgroupKind::Slice | CgroupKind::Other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn level(name: &str) -> CgroupLevel {
+        CgroupLevel {
+            name: name.to_string(),
+            kind: CgroupKind::from_name(name),
+            memory_max: None,
+            cpu_max: None,
+            pids_max: None,
+        }
+    }
+
+    #[test]
+    fn test_kind_and_owner() {
+        assert_eq!(CgroupKind::from_name("user-1000.slice"), CgroupKind::Slice);
+        assert_eq!(
+            CgroupKind::from_name("session-3.scope"),
+            CgroupKind::Session
+        );
+        assert_eq!(
+            CgroupKind::from_name("docker-4f3c.scope"),
+            CgroupKind::Container
+        );
+        assert_eq!(CgroupKind::from_name("run-r1.scope"), CgroupKind::Scope);
+        assert_eq!(CgroupKind::from_name("4f3c"), CgroupKind::Other);
+
+        let info = CgroupInfo {
+            version: 2,
+            path: "/system.slice/docker-4f3c.scope/docker/4f3c".into(),
+            levels: ["system.slice", "docker-4f3c.scope", "docker", "4f3c"]
+                .map(level)
+                .to_vec(),
+        };
+        assert_eq!(
+            info.owner().map(|l| l.name.as_str()),
+            Some("docker-4f3c.scope")
+        );
+        assert_eq!(CgroupInfo::default().owner(), None);
+    }
+}
@@ -0,0 +1,111 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The container a process runs in, identified from its cgroup path and,
+/// where the runtime keeps local state, named from it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContainerInfo {
+    pub runtime: ContainerRuntime,
+    pub id: String,
+    /// The first 12 characters of the ID, as runtimes print it.
+    pub short_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pod_uid: Option<String>,
+    /// `guaranteed`, `burstable` or `besteffort`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub qos_class: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pod: Option<super::PodInfo>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<ContainerState>,
+}
+
+/// What the Docker engine records about a container's lifecycle.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContainerState {
+    /// `running`, `restarting`, `paused`, `exited`, ...
+    pub status: String,
+    pub restart_count: u32,
+    /// Unix time of the current start.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<u64>,
+    /// The health check's verdict, when one is configured.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub health: Option<String>,
+    #[serde(default)]
+    pub failing_streak: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_exit: Option<ContainerExit>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compose_project: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compose_service: Option<String>,
+}
+
+/// How the last run ended, while the container is stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContainerExit {
+    pub code: i64,
+    /// Unix time the run ended.
+    pub finished_at: u64,
+    pub oom_killed: bool,
+}
+
+/// The runtime that created a container, as far as its cgroup path tells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ContainerRuntime {
+    Docker,
+    Containerd,
+    CriO,
+    Podman,
+    Lxc,
+    /// A Kubernetes pod whose runtime left no trace in the cgroup path.
+    Kubernetes,
+}
+
+impl fmt::Display for ContainerRuntime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            ContainerRuntime::Docker => "docker",
+            ContainerRuntime::Containerd => "containerd",
+            ContainerRuntime::CriO => "cri-o",
+            ContainerRuntime::Podman => "podman",
+            ContainerRuntime::Lxc => "lxc",
+            ContainerRuntime::Kubernetes => "kubernetes",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl ContainerInfo {
+    /// A container known only by its ID, before any state files are read.
+    pub fn new(runtime: ContainerRuntime, id: &str) -> Self {
+        Self {
+            runtime,
+            id: id.to_string(),
+            short_id: id.chars().take(12).collect(),
+            name: None,
+            image: None,
+            pod_uid: None,
+            qos_class: None,
+            pod: None,
+            state: None,
+        }
+    }
+
+    /// `compose project web, service api` for containers started by
+    /// Compose.
+    pub fn compose_label(&self) -> Option<String> {
+        let state = self.state.as_ref()?;
+        let project = state.compose_project.as_ref()?;
+        Some(match &state.compose_service {
+            Some(service) => format!("compose project {}, service {}", project, service),
+            None => format!("compose project {}", project),
+        })
+    }
+}
@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The crontab entry that launched a process.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CronJob {
+    /// Host path of the crontab, e.g. `/etc/cron.d/backup`.
+    pub file: String,
+    /// 1-based line of the entry within `file`.
+    pub line: usize,
+    /// The schedule as written: `0 2 * * *`, `@daily`, or an anacron
+    /// period such as `every 7 days`.
+    pub schedule: String,
+    /// The user the job runs as, from the system crontab's user column or
+    /// the spool file's name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    pub command: String,
+    /// Unix time the job is next due.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_run: Option<u64>,
+}
+
+impl CronJob {
+    /// `/etc/cron.d/backup:3`.
+    pub fn location(&self) -> String {
+        format!("{}:{}", self.file, self.line)
+    }
+}
+
+impl fmt::Display for CronJob {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({})", self.location(), self.schedule)
+    }
+}
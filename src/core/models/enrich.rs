@@ -0,0 +1,79 @@
+use bitflags::bitflags;
+
+bitflags! {
+    /// Optional parts of a `Process` that cost a subprocess or extra file
+    /// reads to fill in. Identity fields (pid, parent, name, command) are
+    /// always populated.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct Enrich: u32 {
+        const NETWORK = 1 << 0;
+        const GIT = 1 << 1;
+        const SERVICE = 1 << 2;
+        const CONTAINER = 1 << 3;
+        const RESTARTS = 1 << 4;
+        const ENV = 1 << 5;
+        const FILES = 1 << 6;
+        const MEMORY = 1 << 7;
+        const RESOURCES = 1 << 8;
+        const CRON = 1 << 9;
+        const SUPERVISOR = 1 << 10;
+        const INIT = 1 << 11;
+    }
+}
+
+impl Default for Enrich {
+    fn default() -> Self {
+        Enrich::all()
+    }
+}
+
+impl Enrich {
+    /// Parses a comma-separated field list such as `ports,service`.
+    pub fn from_fields(fields: &str) -> Result<Self, String> {
+        let mut enrich = Enrich::empty();
+        for field in fields.split(',').map(str::trim).filter(|f| !f.is_empty()) {
+            enrich |= match field {
+                "ports" | "network" | "sockets" => Enrich::NETWORK,
+                "git" => Enrich::GIT,
+                "service" => Enrich::SERVICE,
+                "container" => Enrich::CONTAINER,
+                "restarts" => Enrich::RESTARTS,
+                "env" => Enrich::ENV,
+                "files" => Enrich::FILES,
+                "memory" | "maps" => Enrich::MEMORY,
+                "resources" => Enrich::RESOURCES,
+                "cron" => Enrich::CRON,
+                "supervisor" => Enrich::SUPERVISOR,
+                "init" => Enrich::INIT,
+                "all" => Enrich::all(),
+                other => return Err(format!("unknown field '{}'", other)),
+            };
+        }
+        Ok(enrich)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_fields() {
+        assert_eq!(
+            Enrich::from_fields("ports,service").unwrap(),
+            Enrich::NETWORK | Enrich::SERVICE
+        );
+        assert_eq!(
+            Enrich::from_fields(" git , env ").unwrap(),
+            Enrich::GIT | Enrich::ENV
+        );
+        assert_eq!(Enrich::from_fields("").unwrap(), Enrich::empty());
+        assert_eq!(Enrich::from_fields("all").unwrap(), Enrich::all());
+        assert!(Enrich::from_fields("ports,bogus").is_err());
+    }
+
+    #[test]
+    fn test_default_is_everything() {
+        assert_eq!(Enrich::default(), Enrich::all());
+    }
+}
@@ -1,9 +1,215 @@
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+// How many deleted files a warning names before summarising the rest.
+const DELETED_SHOWN: usize = 3;
+
+/// The open descriptors of a process and the file state hanging off them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct FileContext {
     pub open_files: u32,
-    pub file_limit: u32,
+    /// Soft `RLIMIT_NOFILE`, or 0 when unknown.
+    pub file_limit: u64,
+    #[serde(default)]
+    pub file_limit_hard: u64,
+    pub fds: Vec<OpenFile>,
+    /// Files the process holds a lock on, with the lock type.
     pub locked_files: Vec<String>,
+    /// Directories watched through inotify.
     pub watched_dirs: Vec<String>,
 }
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpenFile {
+    pub fd: u32,
+    pub kind: FileKind,
+    /// The descriptor's link target, e.g. a path or `socket:[1234]`.
+    pub target: String,
+    pub pos: u64,
+    /// `open(2)` flags as reported by fdinfo.
+    pub flags: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inode: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileKind {
+    File,
+    Socket,
+    Pipe,
+    /// An anonymous inode such as `eventfd`, `inotify` or `[timerfd]`.
+    AnonInode(String),
+    /// Anonymous memory from `memfd_create(2)`, by the name it was given.
+    /// Its link reads `/memfd:<name> (deleted)` but it never was a file.
+    Memfd(String),
+    Other,
+}
+
+impl FileKind {
+    /// Classifies a `/proc/<pid>/fd` link target.
+    pub fn from_target(target: &str) -> Self {
+        if let Some(name) = target.strip_prefix("/memfd:") {
+            FileKind::Memfd(name.trim_end_matches(" (deleted)").to_string())
+        } else if target.starts_with('/') {
+            FileKind::File
+        } else if target.starts_with("socket:") {
+            FileKind::Socket
+        } else if target.starts_with("pipe:") {
+            FileKind::Pipe
+        } else if let Some(name) = target.strip_prefix("anon_inode:") {
+            FileKind::AnonInode(name.trim_matches(['[', ']']).to_string())
+        } else {
+            FileKind::Other
+        }
+    }
+}
+
+impl fmt::Display for FileKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FileKind::File => write!(f, "file"),
+            FileKind::Socket => write!(f, "socket"),
+            FileKind::Pipe => write!(f, "pipe"),
+            FileKind::AnonInode(name) => write!(f, "{}", name),
+            FileKind::Memfd(_) => write!(f, "memfd"),
+            FileKind::Other => write!(f, "other"),
+        }
+    }
+}
+
+impl FileContext {
+    /// Descriptor exhaustion once `fd_usage` of the soft limit is in use,
+    /// and deleted files that are still open and so still take up space.
+    pub fn warnings(&self, fd_usage: f64) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if self.file_limit > 0 && self.open_files as f64 >= self.file_limit as f64 * fd_usage {
+            let hard = match self.file_limit_hard {
+                hard if hard > self.file_limit => format!(", hard limit {}", hard),
+                _ => String::new(),
+            };
+            warnings.push(format!(
+                "FD EXHAUSTION: {} of {} open files in use ({:.0}%{})",
+                self.open_files,
+                self.file_limit,
+                self.open_files as f64 * 100.0 / self.file_limit as f64,
+                hard
+            ));
+        }
+        let deleted: Vec<&OpenFile> = self.fds.iter().filter(|f| f.is_deleted()).collect();
+        match deleted.as_slice() {
+            [] => {}
+            [file] => warnings.push(format!(
+                "Deleted file still open on fd {}: {}",
+                file.fd, file.target
+            )),
+            files => {
+                let shown: Vec<String> = files
+                    .iter()
+                    .take(DELETED_SHOWN)
+                    .map(|f| format!("fd {}: {}", f.fd, f.target))
+                    .collect();
+                let more = match files.len().saturating_sub(DELETED_SHOWN) {
+                    0 => String::new(),
+                    n => format!(" and {} more", n),
+                };
+                warnings.push(format!(
+                    "{} deleted files still open: {}{}",
+                    files.len(),
+                    shown.join(", "),
+                    more
+                ));
+            }
+        }
+        warnings
+    }
+}
+
+impl OpenFile {
+    /// A regular file unlinked while open; its space is only freed on close.
+    pub fn is_deleted(&self) -> bool {
+        self.kind == FileKind::File && self.target.ends_with(" (deleted)")
+    }
+
+    /// `r`, `w` or `rw`, from the access mode bits of the flags.
+    pub fn mode(&self) -> &'static str {
+        match self.flags & 0o3 {
+            0 => "r",
+            1 => "w",
+            _ => "rw",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_kind_from_target() {
+        assert_eq!(FileKind::from_target("/var/log/app.log"), FileKind::File);
+        assert_eq!(FileKind::from_target("socket:[5001]"), FileKind::Socket);
+        assert_eq!(FileKind::from_target("pipe:[9001]"), FileKind::Pipe);
+        assert_eq!(
+            FileKind::from_target("anon_inode:[eventfd]"),
+            FileKind::AnonInode("eventfd".into())
+        );
+        assert_eq!(
+            FileKind::from_target("anon_inode:inotify").to_string(),
+            "inotify"
+        );
+        assert_eq!(FileKind::from_target("net:[4026531840]"), FileKind::Other);
+        assert_eq!(
+            FileKind::from_target("/memfd:wayland-shm (deleted)"),
+            FileKind::Memfd("wayland-shm".into())
+        );
+    }
+
+    #[test]
+    fn test_warnings() {
+        let file = |fd, target: &str| OpenFile {
+            fd,
+            kind: FileKind::from_target(target),
+            target: target.to_string(),
+            pos: 0,
+            flags: 0,
+            inode: None,
+        };
+        let mut ctx = FileContext {
+            open_files: 850,
+            file_limit: 1024,
+            file_limit_hard: 4096,
+            fds: vec![
+                file(3, "/var/log/app.log (deleted)"),
+                file(4, "/var/log/app.log"),
+                file(5, "socket:[1] (deleted)"),
+                file(6, "/memfd:x (deleted)"),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(
+            ctx.warnings(0.8),
+            vec![
+                "FD EXHAUSTION: 850 of 1024 open files in use (83%, hard limit 4096)",
+                "Deleted file still open on fd 3: /var/log/app.log (deleted)",
+            ]
+        );
+        assert_eq!(ctx.warnings(0.9).len(), 1);
+
+        for fd in 7..11 {
+            ctx.fds
+                .push(file(fd, &format!("/tmp/spool.{} (deleted)", fd)));
+        }
+        assert_eq!(
+            ctx.warnings(0.9),
+            vec![
+                "5 deleted files still open: fd 3: /var/log/app.log (deleted), \
+                 fd 7: /tmp/spool.7 (deleted), fd 8: /tmp/spool.8 (deleted) and 2 more"
+            ]
+        );
+
+        ctx.file_limit = 0;
+        ctx.fds.clear();
+        assert!(ctx.warnings(0.0).is_empty());
+    }
+}
@@ -0,0 +1,91 @@
+use super::Process;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// The paths to look for among open files, working directories,
+/// executables and mapped libraries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathQuery {
+    /// Exactly this file or directory.
+    File(PathBuf),
+    /// Anything at or below this directory, e.g. a mountpoint.
+    Under(PathBuf),
+}
+
+impl PathQuery {
+    /// Whether a path as `/proc` reports it falls inside the query. A file
+    /// unlinked while open still counts, since it still pins its filesystem.
+    pub fn matches(&self, path: &str) -> bool {
+        let path = Path::new(path.strip_suffix(" (deleted)").unwrap_or(path));
+        match self {
+            PathQuery::File(file) => path == file,
+            PathQuery::Under(dir) => path.starts_with(dir),
+        }
+    }
+}
+
+impl fmt::Display for PathQuery {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PathQuery::File(path) | PathQuery::Under(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// How a process keeps a path in use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PathUse {
+    Fd(u32),
+    Cwd,
+    Root,
+    Exe,
+    /// Mapped into memory, usually as a shared library.
+    Mmap,
+}
+
+impl fmt::Display for PathUse {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PathUse::Fd(fd) => write!(f, "fd {}", fd),
+            PathUse::Cwd => write!(f, "cwd"),
+            PathUse::Root => write!(f, "root"),
+            PathUse::Exe => write!(f, "exe"),
+            PathUse::Mmap => write!(f, "mmap"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HeldPath {
+    pub usage: PathUse,
+    pub path: String,
+}
+
+/// A process holding a looked-up path, with every way it holds it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct PathHolder {
+    pub process: Process,
+    pub paths: Vec<HeldPath>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path_query_matches() {
+        let file = PathQuery::File("/var/log/app.log".into());
+        assert!(file.matches("/var/log/app.log"));
+        assert!(file.matches("/var/log/app.log (deleted)"));
+        assert!(!file.matches("/var/log/app.log.1"));
+
+        let mount = PathQuery::Under("/mnt/data".into());
+        assert!(mount.matches("/mnt/data"));
+        assert!(mount.matches("/mnt/data/db/wal"));
+        assert!(!mount.matches("/mnt/database"));
+        assert!(!mount.matches("socket:[1234]"));
+        assert_eq!(PathUse::Fd(4).to_string(), "fd 4");
+    }
+}
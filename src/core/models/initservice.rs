@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// An init system or process manager other than systemd.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InitSystem {
+    Pm2,
+    Runit,
+    S6,
+    OpenRc,
+    SysV,
+}
+
+/// The service that an [`InitSystem`] runs a process as.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InitService {
+    pub manager: InitSystem,
+    /// The service directory's name, the init script's name or the PM2 app
+    /// name.
+    pub name: String,
+    /// The file defining the service: a service directory's `run` script,
+    /// an `/etc/init.d` script or PM2's `dump.pm2`.
+    pub config: String,
+    /// The pid file that ties the process to the service.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pidfile: Option<String>,
+    /// What the manager does when the process exits, e.g. `always`,
+    /// `respawn, max 10 in 60s` or `never`.
+    pub restart: String,
+}
+
+impl fmt::Display for InitSystem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            InitSystem::Pm2 => "pm2",
+            InitSystem::Runit => "runit",
+            InitSystem::S6 => "s6",
+            InitSystem::OpenRc => "openrc",
+            InitSystem::SysV => "sysv",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl fmt::Display for InitService {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} ({})", self.manager, self.name, self.config)
+    }
+}
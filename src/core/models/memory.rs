@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+
+/// Where a process's memory goes, from `smaps_rollup`, and what is mapped
+/// into it, from `maps`. Sizes are in bytes.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MemoryContext {
+    pub rss: u64,
+    /// Resident memory with shared pages divided among their users.
+    pub pss: u64,
+    pub pss_anon: u64,
+    pub pss_file: u64,
+    pub shared: u64,
+    pub private: u64,
+    pub swap: u64,
+    /// Files mapped executable other than the main binary, usually shared
+    /// objects.
+    pub libraries: Vec<String>,
+    /// Address ranges of executable mappings no file on disk backs:
+    /// anonymous memory or `memfd` files.
+    pub anon_exec: Vec<String>,
+}
+
+const TEMP_DIRS: &[&str] = &["/tmp/", "/var/tmp/", "/dev/shm/"];
+
+impl MemoryContext {
+    /// Code loaded from temporary directories and executable memory without
+    /// a backing file. JIT runtimes create the latter legitimately, so it is
+    /// reported once rather than per mapping.
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings: Vec<String> = self
+            .libraries
+            .iter()
+            .filter(|lib| TEMP_DIRS.iter().any(|dir| lib.starts_with(dir)))
+            .map(|lib| {
+                format!(
+                    "INJECTED LIBRARY: {} loaded from a temporary directory",
+                    lib
+                )
+            })
+            .collect();
+        if let Some(first) = self.anon_exec.first() {
+            warnings.push(format!(
+                "ANONYMOUS EXECUTABLE MEMORY: {} mapping(s) with no backing file, first at {}",
+                self.anon_exec.len(),
+                first
+            ));
+        }
+        warnings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_warnings() {
+        let mut ctx = MemoryContext {
+            libraries: vec![
+                "/usr/lib/libc.so.6".into(),
+                "/dev/shm/.x/libhook.so".into(),
+                "/tmpfs/lib.so".into(),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(
+            ctx.warnings(),
+            vec!["INJECTED LIBRARY: /dev/shm/.x/libhook.so loaded from a temporary directory"]
+        );
+
+        ctx.libraries.clear();
+        ctx.anon_exec = vec!["7f00-7f10 rwxp".into(), "7f20-7f30 r-xp /memfd:x".into()];
+        assert_eq!(
+            ctx.warnings(),
+            vec![
+                "ANONYMOUS EXECUTABLE MEMORY: 2 mapping(s) with no backing file, first at 7f00-7f10 rwxp"
+            ]
+        );
+    }
+}
@@ -1,15 +1,41 @@
+mod cgroup;
+mod container;
+mod cron;
+mod enrich;
 mod filecontext;
+mod holder;
+mod initservice;
+mod memory;
+mod owner;
+mod pod;
 mod process;
+mod remote;
 mod resource;
 mod result;
 mod socket;
 mod source;
+mod supervisor;
 mod target;
+mod thresholds;
+mod unit;
 
-pub use filecontext::FileContext;
+pub use cgroup::{CgroupInfo, CgroupKind, CgroupLevel};
+pub use container::{ContainerExit, ContainerInfo, ContainerRuntime, ContainerState};
+pub use cron::CronJob;
+pub use enrich::Enrich;
+pub use filecontext::{FileContext, FileKind, OpenFile};
+pub use holder::{HeldPath, PathHolder, PathQuery, PathUse};
+pub use initservice::{InitService, InitSystem};
+pub use memory::MemoryContext;
+pub use owner::SocketOwner;
+pub use pod::{PodInfo, PodOwner};
 pub use process::Process;
-pub use resource::ResourceContext;
+pub use remote::RemoteFilter;
+pub use resource::{CpuThrottling, Pressure, ResourceContext};
 pub use result::InspectionResult;
-pub use socket::SocketInfo;
+pub use socket::{SocketInfo, TcpInfo};
 pub use source::{Source, SourceType};
+pub use supervisor::{SupervisorProgram, SupervisorState};
 pub use target::{Target, TargetType};
+pub use thresholds::Thresholds;
+pub use unit::{SystemdUnit, TriggerKind, UnitTrigger};
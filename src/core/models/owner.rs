@@ -0,0 +1,11 @@
+use super::{Process, SocketInfo};
+use serde::{Deserialize, Serialize};
+
+/// A process holding a looked-up port, with the sockets on that port it
+/// holds. Several processes can own one port: prefork workers sharing a
+/// listener, `SO_REUSEPORT` groups, or separate IPv4 and IPv6 binds.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct SocketOwner {
+    pub process: Process,
+    pub sockets: Vec<SocketInfo>,
+}
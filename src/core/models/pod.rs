@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+
+/// The Kubernetes pod a container belongs to, as far as the node's own state
+/// describes it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PodInfo {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub container: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<PodOwner>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub restart_count: Option<u32>,
+}
+
+/// The workload that created the pod, e.g. a Deployment or DaemonSet.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PodOwner {
+    pub kind: String,
+    pub name: String,
+    /// Guessed from the pod's generated name rather than read from its
+    /// owner references.
+    #[serde(default)]
+    pub inferred: bool,
+}
+
+impl PodInfo {
+    /// `pod default/api-7d9f8b6c5-x2x9k, Deployment api`.
+    pub fn label(&self) -> Option<String> {
+        let name = self.name.as_ref()?;
+        let mut label = match &self.namespace {
+            Some(ns) => format!("pod {}/{}", ns, name),
+            None => format!("pod {}", name),
+        };
+        if let Some(owner) = &self.owner {
+            label.push_str(&format!(", {} {}", owner.kind, owner.name));
+        }
+        Some(label)
+    }
+}
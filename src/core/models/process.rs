@@ -14,9 +14,19 @@ pub struct Process {
     pub git_repo: Option<String>,
     pub git_branch: Option<String>,
     pub container: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub container_info: Option<super::ContainerInfo>,
     pub service: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub service_file: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit: Option<super::SystemdUnit>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cron: Option<super::CronJob>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub supervisor: Option<super::SupervisorProgram>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub init: Option<super::InitService>,
     pub ports: Vec<u16>,
     pub bind_addrs: Vec<String>,
     #[serde(default)]
@@ -31,4 +41,66 @@ pub struct Process {
     pub cpu_usage: f32,
     #[serde(default)]
     pub memory_usage: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub files: Option<super::FileContext>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory: Option<super::MemoryContext>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resources: Option<super::ResourceContext>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cgroup: Option<super::CgroupInfo>,
+}
+
+impl Process {
+    /// Named unix sockets, one per path, preferring the listening one.
+    pub fn unix_sockets(&self) -> Vec<&super::SocketInfo> {
+        let mut named: Vec<&super::SocketInfo> = self
+            .sockets
+            .iter()
+            .filter(|s| s.unix_path.is_some())
+            .collect();
+        named.sort_by(|a, b| {
+            (&a.unix_path, !a.is_listening()).cmp(&(&b.unix_path, !b.is_listening()))
+        });
+        named.dedup_by(|a, b| a.unix_path == b.unix_path);
+        named
+    }
+
+    /// `LD_PRELOAD` from the environment, when it was looked up and is set.
+    pub fn ld_preload(&self) -> Option<&str> {
+        self.env
+            .iter()
+            .find_map(|var| var.strip_prefix("LD_PRELOAD="))
+            .filter(|libs| !libs.is_empty())
+    }
+
+    /// Findings from the optional contexts: open files, memory mappings and
+    /// preloaded libraries.
+    pub fn context_warnings(&self, thresholds: &super::Thresholds) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if let Some(files) = &self.files {
+            warnings.extend(files.warnings(thresholds.fd_usage));
+        }
+        if let Some(memory) = &self.memory {
+            warnings.extend(memory.warnings());
+        }
+        if let Some(libs) = self.ld_preload() {
+            warnings.push(format!("LD_PRELOAD set: {}", libs));
+        }
+        warnings
+    }
+
+    /// What started the process's container: its Compose project and
+    /// service or Kubernetes pod when known, otherwise the runtime.
+    pub fn container_source(&self) -> Option<String> {
+        let info = self.container_info.as_ref();
+        info.and_then(|c| c.compose_label())
+            .or_else(|| info.and_then(|c| c.pod.as_ref()?.label()))
+            .or_else(|| self.container.clone())
+    }
+
+    /// Network namespace of the process's sockets, when it is not the host's.
+    pub fn netns(&self) -> Option<u64> {
+        self.sockets.iter().find_map(|s| s.netns)
+    }
 }
@@ -0,0 +1,131 @@
+use std::fmt;
+use std::net::IpAddr;
+
+/// A remote endpoint to look for among established connections: a single
+/// address or a CIDR block, optionally narrowed to one port.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RemoteFilter {
+    network: IpAddr,
+    prefix: u8,
+    port: Option<u16>,
+}
+
+impl RemoteFilter {
+    /// Parses `10.0.0.5`, `10.0.0.5:5432`, `10.0.0.0/24:5432`, `fd00::/8`
+    /// or `[::1]:5432`.
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let invalid = || format!("invalid remote endpoint '{}'", input);
+        let (addr, port) = if let Some(rest) = input.strip_prefix('[') {
+            let (addr, tail) = rest.split_once(']').ok_or_else(invalid)?;
+            match tail {
+                "" => (addr, None),
+                _ => (addr, Some(tail.strip_prefix(':').ok_or_else(invalid)?)),
+            }
+        } else if input.matches(':').count() == 1 {
+            let (addr, port) = input.split_once(':').ok_or_else(invalid)?;
+            (addr, Some(port))
+        } else {
+            (input, None)
+        };
+
+        let port = port
+            .map(|p| p.parse::<u16>().map_err(|_| invalid()))
+            .transpose()?;
+        let (ip, prefix) = match addr.split_once('/') {
+            Some((ip, prefix)) => (ip, Some(prefix.parse::<u8>().map_err(|_| invalid())?)),
+            None => (addr, None),
+        };
+        let network = ip.parse::<IpAddr>().map_err(|_| invalid())?;
+        let bits = max_prefix(&network);
+        let prefix = prefix.unwrap_or(bits);
+        if prefix > bits {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            network: mask(network, prefix),
+            prefix,
+            port,
+        })
+    }
+
+    /// Whether an `ip:port` address, as in `SocketInfo::remote_addr`, falls
+    /// inside this filter. IPv4-mapped IPv6 addresses match their IPv4 form.
+    pub fn matches(&self, addr: &str) -> bool {
+        let Some((ip, port)) = addr.rsplit_once(':') else {
+            return false;
+        };
+        let Ok(ip) = ip.parse::<IpAddr>() else {
+            return false;
+        };
+        let ip = ip.to_canonical();
+        let port_matches = self.port.is_none_or(|p| port.parse() == Ok(p));
+        port_matches
+            && max_prefix(&ip) == max_prefix(&self.network)
+            && mask(ip, self.prefix) == self.network
+    }
+}
+
+impl fmt::Display for RemoteFilter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut addr = self.network.to_string();
+        if self.prefix != max_prefix(&self.network) {
+            addr = format!("{}/{}", addr, self.prefix);
+        }
+        match (self.port, self.network) {
+            (Some(port), IpAddr::V6(_)) => write!(f, "[{}]:{}", addr, port),
+            (Some(port), IpAddr::V4(_)) => write!(f, "{}:{}", addr, port),
+            (None, _) => write!(f, "{}", addr),
+        }
+    }
+}
+
+fn max_prefix(ip: &IpAddr) -> u8 {
+    match ip {
+        IpAddr::V4(_) => 32,
+        IpAddr::V6(_) => 128,
+    }
+}
+
+fn mask(ip: IpAddr, prefix: u8) -> IpAddr {
+    let shift = (max_prefix(&ip) - prefix) as u32;
+    match ip {
+        IpAddr::V4(v4) => {
+            IpAddr::V4((u32::from(v4) & u32::MAX.checked_shl(shift).unwrap_or(0)).into())
+        }
+        IpAddr::V6(v6) => {
+            IpAddr::V6((u128::from(v6) & u128::MAX.checked_shl(shift).unwrap_or(0)).into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_match() {
+        let db = RemoteFilter::parse("10.0.0.5:5432").unwrap();
+        assert!(db.matches("10.0.0.5:5432"));
+        assert!(db.matches("::ffff:10.0.0.5:5432"));
+        assert!(!db.matches("10.0.0.5:5433"));
+        assert!(!db.matches("10.0.0.6:5432"));
+
+        let subnet = RemoteFilter::parse("10.0.0.0/24").unwrap();
+        assert!(subnet.matches("10.0.0.200:40000"));
+        assert!(!subnet.matches("10.0.1.1:40000"));
+        assert_eq!(subnet.to_string(), "10.0.0.0/24");
+
+        let v6 = RemoteFilter::parse("[fd00::1/8]:443").unwrap();
+        assert!(v6.matches("fd12::7:443"));
+        assert!(!v6.matches("fe80::1:443"));
+        assert_eq!(v6.to_string(), "[fd00::/8]:443");
+
+        assert!(RemoteFilter::parse("::1").unwrap().matches("::1:22"));
+        assert!(RemoteFilter::parse("0.0.0.0/0")
+            .unwrap()
+            .matches("8.8.8.8:53"));
+        assert!(RemoteFilter::parse("10.0.0.0/33").is_err());
+        assert!(RemoteFilter::parse("db.internal:5432").is_err());
+    }
+}
@@ -1,9 +1,49 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Limits and contention affecting a process: its cgroup's memory and CPU
+/// limits and pressure stall information, its OOM-killer standing and any
+/// sleep inhibitor it holds.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct ResourceContext {
-    pub energy_impact: String,
+    /// The cgroup the limits and pressure were read from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cgroup: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_current: Option<u64>,
+    /// `None` when memory is unlimited.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_max: Option<u64>,
+    /// CPU quota in CPUs, e.g. `0.5`; `None` when unlimited.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu_max: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu_throttling: Option<CpuThrottling>,
+    #[serde(default)]
+    pub pressure: Vec<Pressure>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub oom_score: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub oom_score_adj: Option<i32>,
     pub prevents_sleep: bool,
-    pub thermal_state: String,
-    pub app_napped: bool,
+    /// Inhibitor locks held, e.g. `sleep (block): Backup in progress`.
+    #[serde(default)]
+    pub inhibitors: Vec<String>,
+}
+
+/// Enforcement periods from `cpu.stat` in which the quota ran out.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CpuThrottling {
+    pub periods: u64,
+    pub throttled: u64,
+    pub throttled_usec: u64,
+}
+
+/// Share of the last 10 seconds tasks stalled waiting on `resource`:
+/// `some` for at least one task, `full` for all of them at once.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Pressure {
+    pub resource: String,
+    pub some: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub full: Option<f64>,
 }
@@ -1,4 +1,4 @@
-use super::{Process, Source, SourceType};
+use super::{Process, Source, SourceType, Thresholds};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,8 +12,16 @@ pub struct InspectionResult {
 
 impl InspectionResult {
     pub fn new(process: Process, ancestry: Vec<Process>) -> Self {
+        Self::with_thresholds(process, ancestry, &Thresholds::default())
+    }
+
+    pub fn with_thresholds(
+        process: Process,
+        ancestry: Vec<Process>,
+        thresholds: &Thresholds,
+    ) -> Self {
         let source = Self::detect_source(&process, &ancestry);
-        let warnings = Self::generate_warnings(&process, &ancestry);
+        let warnings = Self::generate_warnings(&process, &ancestry, thresholds);
         let restart_count = 0;
 
         Self {
@@ -26,6 +34,28 @@ impl InspectionResult {
     }
 
     fn detect_source(process: &Process, ancestry: &[Process]) -> Source {
+        // Cron jobs, supervisord programs and PM2 apps run inside their
+        // manager's own service, so the job or program is the more specific
+        // answer.
+        if let Some(job) = &process.cron {
+            return Source {
+                source_type: SourceType::Cron,
+                name: Some(job.location()),
+            };
+        }
+        if let Some(program) = &process.supervisor {
+            return Source {
+                source_type: SourceType::Supervisor,
+                name: Some(program.to_string()),
+            };
+        }
+        if let Some(init) = &process.init {
+            return Source {
+                source_type: init.manager.into(),
+                name: Some(format!("{} ({})", init.name, init.config)),
+            };
+        }
+
         if let Some(service) = &process.service {
             if service.ends_with(".service") {
                 return Source {
@@ -44,7 +74,7 @@ impl InspectionResult {
         if process.container.is_some() {
             return Source {
                 source_type: SourceType::Docker,
-                name: process.container.clone(),
+                name: process.container_source(),
             };
         }
 
@@ -82,7 +112,11 @@ impl InspectionResult {
         }
     }
 
-    fn generate_warnings(process: &Process, ancestry: &[Process]) -> Vec<String> {
+    fn generate_warnings(
+        process: &Process,
+        ancestry: &[Process],
+        thresholds: &Thresholds,
+    ) -> Vec<String> {
         let mut warnings = Vec::new();
 
         if process.health != "healthy" {
@@ -115,6 +149,8 @@ impl InspectionResult {
             }
         }
 
+        warnings.extend(process.context_warnings(thresholds));
+
         let now = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap_or_default()
@@ -165,7 +201,6 @@ mod tests {
 
     fn mock_process(pid: u32, name: &str) -> Process {
         Process {
-            sockets: Vec::new(),
             pid,
             parent_pid: Some(1),
             name: name.to_string(),
@@ -175,20 +210,9 @@ mod tests {
             username: Some("user".to_string()),
             start_time: 1000,
             cwd: Some("/home/user".to_string()),
-            git_repo: None,
-            git_branch: None,
-            container: None,
-            service: None,
-            service_file: None,
-            ports: vec![],
-            bind_addrs: vec![],
-            port_states: vec![],
-            restart_count: None,
             health: "healthy".to_string(),
             forked: "forked".to_string(),
-            env: vec![],
-            cpu_usage: 0.0,
-            memory_usage: 0,
+            ..Default::default()
         }
     }
 
@@ -210,6 +234,62 @@ mod tests {
         assert_eq!(result.source.source_type, SourceType::Docker);
     }
 
+    #[test]
+    fn test_source_detection_cron() {
+        let mut process = mock_process(100, "backup.sh");
+        process.service = Some("cron.service".to_string());
+        process.cron = Some(crate::core::models::CronJob {
+            file: "/etc/cron.d/backup".to_string(),
+            line: 3,
+            ..Default::default()
+        });
+        let ancestry = vec![process.clone()];
+        let result = InspectionResult::new(process, ancestry);
+        assert_eq!(result.source.source_type, SourceType::Cron);
+        assert_eq!(result.source.name.as_deref(), Some("/etc/cron.d/backup:3"));
+    }
+
+    #[test]
+    fn test_source_detection_supervisor() {
+        let mut process = mock_process(100, "php");
+        process.service = Some("supervisor.service".to_string());
+        process.supervisor = Some(crate::core::models::SupervisorProgram {
+            program: "worker".to_string(),
+            process: "worker_01".to_string(),
+            group: "worker".to_string(),
+            config: "/etc/supervisor/conf.d/worker.conf".to_string(),
+            line: 1,
+            ..Default::default()
+        });
+        let ancestry = vec![process.clone()];
+        let result = InspectionResult::new(process, ancestry);
+        assert_eq!(result.source.source_type, SourceType::Supervisor);
+        assert_eq!(
+            result.source.name.as_deref(),
+            Some("worker:worker_01 (/etc/supervisor/conf.d/worker.conf:1)")
+        );
+    }
+
+    #[test]
+    fn test_source_detection_init_service() {
+        let mut process = mock_process(100, "node");
+        process.service = Some("pm2-root.service".to_string());
+        process.init = Some(crate::core::models::InitService {
+            manager: crate::core::models::InitSystem::Pm2,
+            name: "api".to_string(),
+            config: "/root/.pm2/dump.pm2".to_string(),
+            pidfile: Some("/root/.pm2/pids/api-0.pid".to_string()),
+            restart: "always".to_string(),
+        });
+        let ancestry = vec![process.clone()];
+        let result = InspectionResult::new(process, ancestry);
+        assert_eq!(result.source.source_type, SourceType::PM2);
+        assert_eq!(
+            result.source.name.as_deref(),
+            Some("api (/root/.pm2/dump.pm2)")
+        );
+    }
+
     #[test]
     fn test_warnings_root() {
         let mut process = mock_process(100, "myapp");
@@ -250,6 +330,25 @@ mod tests {
             .any(|w| w.contains("SUSPICIOUS LOCATION")));
     }
 
+    #[test]
+    fn test_warnings_fd_threshold() {
+        let mut process = mock_process(100, "myapp");
+        process.files = Some(crate::core::models::FileContext {
+            open_files: 700,
+            file_limit: 1024,
+            ..Default::default()
+        });
+        let fd_warning = |fd_usage| {
+            let thresholds = Thresholds { fd_usage };
+            InspectionResult::with_thresholds(process.clone(), vec![], &thresholds)
+                .warnings
+                .iter()
+                .any(|w| w.contains("FD EXHAUSTION"))
+        };
+        assert!(!fd_warning(0.8));
+        assert!(fd_warning(0.5));
+    }
+
     #[test]
     fn test_warnings_reverse_shell() {
         let parent = mock_process(50, "nginx");
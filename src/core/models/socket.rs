@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct SocketInfo {
@@ -6,12 +7,42 @@ pub struct SocketInfo {
     pub state: String,
     pub local_addr: String,
     pub remote_addr: String,
+    /// One of `tcp`, `udp`, `raw` or `unix`.
+    #[serde(default)]
+    pub protocol: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unix_path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uid: Option<u32>,
+    #[serde(default)]
+    pub rx_queue: u32,
+    #[serde(default)]
+    pub tx_queue: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tcp_info: Option<TcpInfo>,
+    /// Inode of the network namespace, set when the socket lives outside
+    /// the host's namespace, e.g. inside a container.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub netns: Option<u64>,
     #[serde(skip_serializing_if = "String::is_empty")]
     pub explanation: String,
     #[serde(skip_serializing_if = "String::is_empty")]
     pub workaround: String,
 }
 
+/// Kernel TCP statistics for one connection, from `struct tcp_info`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct TcpInfo {
+    pub rtt_us: u32,
+    pub rttvar_us: u32,
+    pub snd_cwnd: u32,
+    pub unacked: u32,
+    pub lost: u32,
+    pub total_retrans: u32,
+    pub bytes_acked: Option<u64>,
+    pub bytes_received: Option<u64>,
+}
+
 impl SocketInfo {
     pub fn new(port: u16, state: String, local_addr: String, remote_addr: String) -> Self {
         let mut socket = Self {
@@ -19,6 +50,13 @@ impl SocketInfo {
             state: state.clone(),
             local_addr,
             remote_addr,
+            protocol: "tcp".to_string(),
+            unix_path: None,
+            uid: None,
+            rx_queue: 0,
+            tx_queue: 0,
+            tcp_info: None,
+            netns: None,
             explanation: String::new(),
             workaround: String::new(),
         };
@@ -26,6 +64,46 @@ impl SocketInfo {
         socket
     }
 
+    /// A unix domain socket; `path` is `None` for unnamed sockets.
+    pub fn unix(path: Option<String>, state: String) -> Self {
+        let mut socket = Self::new(0, state, path.clone().unwrap_or_default(), String::new());
+        socket.protocol = "unix".to_string();
+        socket.unix_path = path;
+        socket
+    }
+
+    pub fn with_protocol(mut self, protocol: &str) -> Self {
+        self.protocol = protocol.to_string();
+        self
+    }
+
+    /// A TCP or UDP socket, i.e. one that can be looked up by port.
+    pub fn is_inet(&self) -> bool {
+        matches!(self.protocol.as_str(), "tcp" | "udp")
+    }
+
+    /// Accepting connections, or for UDP, bound and receiving datagrams.
+    pub fn is_listening(&self) -> bool {
+        matches!(self.state.as_str(), "LISTEN" | "LISTENING" | "UNCONN")
+    }
+
+    /// The local address without its port.
+    pub fn bind_addr(&self) -> &str {
+        self.local_addr
+            .rsplit_once(':')
+            .map(|(ip, _)| ip)
+            .unwrap_or(&self.local_addr)
+    }
+
+    /// `IPv4` or `IPv6` for inet sockets, otherwise the protocol.
+    pub fn family(&self) -> &str {
+        match self.bind_addr().parse::<IpAddr>() {
+            Ok(IpAddr::V4(_)) if self.is_inet() => "IPv4",
+            Ok(IpAddr::V6(_)) if self.is_inet() => "IPv6",
+            _ => &self.protocol,
+        }
+    }
+
     pub fn enrich_details(&mut self) {
         match self.state.as_str() {
             "LISTEN" | "LISTENING" => {
@@ -47,9 +125,12 @@ impl SocketInfo {
             "FIN_WAIT_2" => {
                 self.explanation = "Local close acknowledged, waiting for remote close".to_string();
             }
-            "ESTABLISHED" => {
+            "ESTABLISHED" | "CONNECTED" => {
                 self.explanation = "Active connection".to_string();
             }
+            "UNCONN" => {
+                self.explanation = "Bound and receiving datagrams".to_string();
+            }
             "SYN_SENT" => {
                 self.explanation = "Connection request sent, waiting for response".to_string();
             }
@@ -76,3 +157,33 @@ impl SocketInfo {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_socket_kinds() {
+        let dns = SocketInfo::new(
+            53,
+            "UNCONN".to_string(),
+            "127.0.0.53:53".to_string(),
+            "0.0.0.0:0".to_string(),
+        )
+        .with_protocol("udp");
+        assert!(dns.is_inet());
+        assert!(dns.is_listening());
+        assert_eq!(dns.bind_addr(), "127.0.0.53");
+        assert_eq!(dns.family(), "IPv4");
+
+        let v6 = SocketInfo::new(80, "LISTEN".into(), ":::80".into(), String::new());
+        assert_eq!(v6.protocol, "tcp");
+        assert_eq!(v6.bind_addr(), "::");
+        assert_eq!(v6.family(), "IPv6");
+
+        let docker = SocketInfo::unix(Some("/run/docker.sock".into()), "LISTEN".into());
+        assert!(!docker.is_inet());
+        assert_eq!(docker.family(), "unix");
+        assert_eq!(docker.unix_path.as_deref(), Some("/run/docker.sock"));
+    }
+}
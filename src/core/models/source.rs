@@ -1,3 +1,4 @@
+use super::InitSystem;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,6 +17,10 @@ pub enum SourceType {
     PM2,
     Supervisor,
     Cron,
+    Runit,
+    S6,
+    OpenRC,
+    SysV,
 }
 
 impl std::fmt::Display for SourceType {
@@ -29,7 +34,23 @@ impl std::fmt::Display for SourceType {
             SourceType::PM2 => "pm2",
             SourceType::Supervisor => "supervisor",
             SourceType::Cron => "cron",
+            SourceType::Runit => "runit",
+            SourceType::S6 => "s6",
+            SourceType::OpenRC => "openrc",
+            SourceType::SysV => "sysv",
         };
         write!(f, "{}", s)
     }
 }
+
+impl From<InitSystem> for SourceType {
+    fn from(manager: InitSystem) -> Self {
+        match manager {
+            InitSystem::Pm2 => SourceType::PM2,
+            InitSystem::Runit => SourceType::Runit,
+            InitSystem::S6 => SourceType::S6,
+            InitSystem::OpenRc => SourceType::OpenRC,
+            InitSystem::SysV => SourceType::SysV,
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The supervisord program a process runs as, from its `[program:x]` block.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SupervisorProgram {
+    /// The `x` of `[program:x]`.
+    pub program: String,
+    /// The process name, after `process_name` templating, e.g. `worker_01`.
+    pub process: String,
+    /// The program's `[group:x]`, or the program itself.
+    pub group: String,
+    /// The config file holding the program's block.
+    pub config: String,
+    /// 1-based line of the `[program:x]` header.
+    pub line: usize,
+    pub command: String,
+    /// `autorestart`: `true`, `false` or `unexpected`.
+    pub autorestart: String,
+    pub numprocs: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<SupervisorState>,
+}
+
+/// What supervisord reports for the process over XML-RPC.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SupervisorState {
+    /// e.g. `RUNNING`, `BACKOFF` or `EXITED`.
+    pub state: String,
+    /// Unix time of the last (re)start.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<u64>,
+    /// Exit status of the previous run.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit_status: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spawn_error: Option<String>,
+}
+
+impl SupervisorProgram {
+    /// `worker:worker_01`, as supervisorctl names the process.
+    pub fn name(&self) -> String {
+        format!("{}:{}", self.group, self.process)
+    }
+}
+
+impl fmt::Display for SupervisorProgram {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({}:{})", self.name(), self.config, self.line)
+    }
+}
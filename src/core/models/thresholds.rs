@@ -0,0 +1,40 @@
+/// Limits at which inspections raise resource warnings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Thresholds {
+    /// Fraction of the soft open-files limit in use before warning.
+    pub fd_usage: f64,
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Self { fd_usage: 0.8 }
+    }
+}
+
+impl Thresholds {
+    /// Parses a percentage such as `80` or `80%` into a fraction.
+    pub fn parse_percent(value: &str) -> Result<f64, String> {
+        let percent: f64 = value
+            .trim()
+            .trim_end_matches('%')
+            .parse()
+            .map_err(|_| format!("invalid percentage '{}'", value))?;
+        if !(0.0..=100.0).contains(&percent) {
+            return Err(format!("percentage out of range: {}", value));
+        }
+        Ok(percent / 100.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_percent() {
+        assert_eq!(Thresholds::parse_percent("80").unwrap(), 0.8);
+        assert_eq!(Thresholds::parse_percent("95%").unwrap(), 0.95);
+        assert!(Thresholds::parse_percent("150").is_err());
+        assert!(Thresholds::parse_percent("lots").is_err());
+    }
+}
@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The systemd unit a process belongs to, named by its cgroup path, and the
+/// properties its manager reports for it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SystemdUnit {
+    /// e.g. `nginx.service`, `getty@tty1.service` or `session-3.scope`.
+    pub name: String,
+    /// UID of the user manager (`user@<uid>.service`) running the unit;
+    /// `None` for the system manager.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fragment_path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub restarts: Option<u32>,
+    /// Unix time the unit last became active.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_since: Option<u64>,
+    /// The `Restart=` policy, e.g. `on-failure`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub restart_policy: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exec_start: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trigger: Option<UnitTrigger>,
+}
+
+/// Why the manager started a unit: another unit that activates it, or the
+/// unit whose dependencies pulled it in.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnitTrigger {
+    pub kind: TriggerKind,
+    pub unit: String,
+    /// A timer's schedule, a socket's listen addresses or a path unit's
+    /// watched paths.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// Unix time a timer last fired.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last: Option<u64>,
+    /// Unix time a timer fires next.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TriggerKind {
+    Socket,
+    Timer,
+    Path,
+    RequiredBy,
+    WantedBy,
+}
+
+impl fmt::Display for UnitTrigger {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let verb = match self.kind {
+            TriggerKind::Socket | TriggerKind::Timer | TriggerKind::Path => "triggered by",
+            TriggerKind::RequiredBy => "required by",
+            TriggerKind::WantedBy => "wanted by",
+        };
+        write!(f, "{} {}", verb, self.unit)?;
+        if let Some(detail) = &self.detail {
+            write!(f, " ({})", detail)?;
+        }
+        Ok(())
+    }
+}
+
+impl SystemdUnit {
+    /// Services run a command; scopes and slices only group processes.
+    pub fn is_service(&self) -> bool {
+        self.name.ends_with(".service")
+    }
+}
@@ -1,4 +1,4 @@
-use crate::core::models::Process;
+use crate::core::models::{Enrich, PathHolder, PathQuery, Process, RemoteFilter, SocketOwner};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -10,11 +10,34 @@ pub enum SystemError {
 }
 
 #[cfg_attr(test, mockall::automock)]
-pub trait SystemProvider {
-    fn get_process_by_pid(&self, pid: u32) -> Result<Process, SystemError>;
-    fn find_processes_by_name(&self, name: &str) -> Result<Vec<Process>, SystemError>;
-    fn find_process_by_port(&self, port: u16) -> Result<Process, SystemError>;
+pub trait SystemProvider: Send + Sync {
+    fn get_process_by_pid(&self, pid: u32, enrich: Enrich) -> Result<Process, SystemError>;
+    fn find_processes_by_name(
+        &self,
+        name: &str,
+        enrich: Enrich,
+    ) -> Result<Vec<Process>, SystemError>;
+    /// Every process holding a TCP or UDP socket on `port`, ordered by PID.
+    fn find_processes_by_port(
+        &self,
+        port: u16,
+        enrich: Enrich,
+    ) -> Result<Vec<SocketOwner>, SystemError>;
+    /// Every process with an established connection to `remote`.
+    fn find_processes_by_remote(
+        &self,
+        remote: &RemoteFilter,
+        enrich: Enrich,
+    ) -> Result<Vec<SocketOwner>, SystemError>;
+    /// Every process keeping a path matching `query` in use: open, as its
+    /// working or root directory, as its executable or mapped into memory.
+    fn find_processes_by_path(
+        &self,
+        query: &PathQuery,
+        enrich: Enrich,
+    ) -> Result<Vec<PathHolder>, SystemError>;
     fn get_all_pids(&self) -> Result<Vec<u32>, SystemError>;
+    fn refresh(&self) -> Result<(), SystemError>;
 }
 
 #[cfg(test)]
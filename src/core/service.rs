@@ -1,43 +1,90 @@
-use crate::core::models::Process;
+use crate::core::engine::InspectionEngine;
+use crate::core::models::{
+    Enrich, PathHolder, PathQuery, Process, RemoteFilter, SocketOwner, Thresholds,
+};
 use crate::core::ports::{SystemError, SystemProvider};
 use std::sync::Arc;
 
 pub struct WitrService<S: SystemProvider> {
     sys: Arc<S>,
+    enrich: Enrich,
+    thresholds: Thresholds,
 }
 
 impl<S: SystemProvider> WitrService<S> {
     pub fn new(sys: S) -> Self {
-        Self { sys: Arc::new(sys) }
+        Self {
+            sys: Arc::new(sys),
+            enrich: Enrich::default(),
+            thresholds: Thresholds::default(),
+        }
+    }
+
+    /// Limits the lookups performed for every inspected process.
+    pub fn with_enrich(mut self, enrich: Enrich) -> Self {
+        self.enrich = enrich;
+        self
+    }
+
+    /// Sets the limits at which inspections raise resource warnings.
+    pub fn with_thresholds(mut self, thresholds: Thresholds) -> Self {
+        self.thresholds = thresholds;
+        self
+    }
+
+    pub fn thresholds(&self) -> Thresholds {
+        self.thresholds
+    }
+
+    pub fn enrich(&self) -> Enrich {
+        self.enrich
     }
 
     pub fn inspect_pid(&self, pid: u32) -> Result<Process, SystemError> {
-        self.sys.get_process_by_pid(pid)
+        self.sys.get_process_by_pid(pid, self.enrich)
     }
 
     pub fn inspect_name(&self, name: &str) -> Result<Vec<Process>, SystemError> {
-        self.sys.find_processes_by_name(name)
+        self.sys.find_processes_by_name(name, self.enrich)
     }
 
-    pub fn inspect_port(&self, port: u16) -> Result<Process, SystemError> {
-        self.sys.find_process_by_port(port)
+    pub fn inspect_port(&self, port: u16) -> Result<Vec<SocketOwner>, SystemError> {
+        self.sys.find_processes_by_port(port, self.enrich)
+    }
+
+    pub fn inspect_remote(&self, remote: &RemoteFilter) -> Result<Vec<SocketOwner>, SystemError> {
+        self.sys.find_processes_by_remote(remote, self.enrich)
+    }
+
+    pub fn inspect_path(&self, query: &PathQuery) -> Result<Vec<PathHolder>, SystemError> {
+        self.sys.find_processes_by_path(query, self.enrich)
     }
 
     pub fn get_all_pids(&self) -> Result<Vec<u32>, SystemError> {
         self.sys.get_all_pids()
     }
 
+    pub fn refresh(&self) -> Result<(), SystemError> {
+        self.sys.refresh()
+    }
+
     pub fn inspect_all(&self) -> Result<Vec<crate::core::models::InspectionResult>, SystemError> {
         let pids = self.sys.get_all_pids()?;
-        let mut results = Vec::new();
-        for pid in pids {
-            if let Ok(res) = self.get_inspection(pid) {
-                if !res.warnings.is_empty() {
-                    results.push(res);
-                }
-            }
-        }
-        Ok(results)
+        let engine = self.engine();
+        Ok(engine
+            .inspect_many(&pids)
+            .into_iter()
+            .filter(|res| !res.warnings.is_empty())
+            .collect())
+    }
+
+    pub fn inspect_many(&self, pids: &[u32]) -> Vec<crate::core::models::InspectionResult> {
+        self.engine().inspect_many(pids)
+    }
+
+    pub fn list_processes(&self) -> Result<Vec<Process>, SystemError> {
+        let pids = self.sys.get_all_pids()?;
+        Ok(self.engine().processes(&pids))
     }
 
     pub fn get_inspection(
@@ -46,11 +93,17 @@ impl<S: SystemProvider> WitrService<S> {
     ) -> Result<crate::core::models::InspectionResult, SystemError> {
         let process = self.inspect_pid(pid)?;
         let ancestry = self.get_ancestry(pid)?;
-        Ok(crate::core::models::InspectionResult::new(
-            process, ancestry,
+        Ok(crate::core::models::InspectionResult::with_thresholds(
+            process,
+            ancestry,
+            &self.thresholds,
         ))
     }
 
+    fn engine(&self) -> InspectionEngine<'_, S> {
+        InspectionEngine::new(self.sys.as_ref(), self.enrich).with_thresholds(self.thresholds)
+    }
+
     pub fn get_ancestry(&self, pid: u32) -> Result<Vec<Process>, SystemError> {
         let mut chain = Vec::new();
         let mut current_pid = Some(pid);
@@ -61,7 +114,7 @@ impl<S: SystemProvider> WitrService<S> {
                 break;
             }
 
-            match self.sys.get_process_by_pid(pid) {
+            match self.sys.get_process_by_pid(pid, self.enrich) {
                 Ok(p) => {
                     current_pid = p.parent_pid;
                     chain.push(p);
@@ -83,34 +136,17 @@ mod tests {
     fn test_inspect_pid_found() {
         let mut mock = MockSystemProvider::new();
         mock.expect_get_process_by_pid()
-            .with(mockall::predicate::eq(123))
+            .with(mockall::predicate::eq(123), mockall::predicate::always())
             .times(1)
-            .returning(|_| {
+            .returning(|_, _| {
                 Ok(Process {
                     pid: 123,
                     parent_pid: Some(1),
                     name: "test".to_string(),
                     cmd: vec!["test".to_string()],
-                    exe_path: None,
-                    uid: None,
-                    username: None,
-                    start_time: 0,
-                    cwd: None,
-                    git_repo: None,
-                    git_branch: None,
-                    container: None,
-                    service: None,
-                    service_file: None,
-                    ports: vec![],
-                    bind_addrs: vec![],
-                    port_states: vec![],
-                    sockets: vec![],
-                    restart_count: None,
                     health: "healthy".into(),
                     forked: "unknown".into(),
-                    env: vec![],
-                    cpu_usage: 0.0,
-                    memory_usage: 0,
+                    ..Default::default()
                 })
             });
 
@@ -126,95 +162,40 @@ mod tests {
 
         // Target process 100 -> Parent 50 -> Root 1
         mock.expect_get_process_by_pid()
-            .with(mockall::predicate::eq(100))
-            .returning(|_| {
+            .with(mockall::predicate::eq(100), mockall::predicate::always())
+            .returning(|_, _| {
                 Ok(Process {
                     pid: 100,
                     parent_pid: Some(50),
                     name: "target".into(),
-                    cmd: vec![],
-                    exe_path: None,
-                    uid: None,
-                    username: None,
-                    start_time: 0,
-                    cwd: None,
-                    git_repo: None,
-                    git_branch: None,
-                    container: None,
-                    service: None,
-                    service_file: None,
-                    ports: vec![],
-                    bind_addrs: vec![],
-                    port_states: vec![],
-                    sockets: vec![],
-                    restart_count: None,
                     health: "healthy".into(),
                     forked: "unknown".into(),
-                    env: vec![],
-                    cpu_usage: 0.0,
-                    memory_usage: 0,
+                    ..Default::default()
                 })
             });
 
         mock.expect_get_process_by_pid()
-            .with(mockall::predicate::eq(50))
-            .returning(|_| {
+            .with(mockall::predicate::eq(50), mockall::predicate::always())
+            .returning(|_, _| {
                 Ok(Process {
                     pid: 50,
                     parent_pid: Some(1),
                     name: "parent".into(),
-                    cmd: vec![],
-                    exe_path: None,
-                    uid: None,
-                    username: None,
-                    start_time: 0,
-                    cwd: None,
-                    git_repo: None,
-                    git_branch: None,
-                    container: None,
-                    service: None,
-                    service_file: None,
-                    ports: vec![],
-                    bind_addrs: vec![],
-                    port_states: vec![],
-                    sockets: vec![],
-                    restart_count: None,
                     health: "healthy".into(),
                     forked: "unknown".into(),
-                    env: vec![],
-                    cpu_usage: 0.0,
-                    memory_usage: 0,
+                    ..Default::default()
                 })
             });
 
         mock.expect_get_process_by_pid()
-            .with(mockall::predicate::eq(1))
-            .returning(|_| {
+            .with(mockall::predicate::eq(1), mockall::predicate::always())
+            .returning(|_, _| {
                 Ok(Process {
                     pid: 1,
-                    parent_pid: None,
                     name: "init".into(),
-                    cmd: vec![],
-                    exe_path: None,
-                    uid: None,
-                    username: None,
-                    start_time: 0,
-                    cwd: None,
-                    git_repo: None,
-                    git_branch: None,
-                    container: None,
-                    service: None,
-                    service_file: None,
-                    ports: vec![],
-                    bind_addrs: vec![],
-                    port_states: vec![],
-                    sockets: vec![],
-                    restart_count: None,
                     health: "healthy".into(),
                     forked: "unknown".into(),
-                    env: vec![],
-                    cpu_usage: 0.0,
-                    memory_usage: 0,
+                    ..Default::default()
                 })
             });
 
@@ -231,12 +212,32 @@ mod tests {
     fn test_inspect_pid_not_found() {
         let mut mock = MockSystemProvider::new();
         mock.expect_get_process_by_pid()
-            .with(mockall::predicate::eq(999))
+            .with(mockall::predicate::eq(999), mockall::predicate::always())
             .times(1)
-            .returning(|_| Err(SystemError::ProcessNotFound("999".to_string())));
+            .returning(|_, _| Err(SystemError::ProcessNotFound("999".to_string())));
 
         let service = WitrService::new(mock);
         let result = service.inspect_pid(999);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_enrich_is_forwarded_to_provider() {
+        let mut mock = MockSystemProvider::new();
+        mock.expect_get_process_by_pid()
+            .with(
+                mockall::predicate::eq(7),
+                mockall::predicate::eq(Enrich::NETWORK | Enrich::SERVICE),
+            )
+            .times(1)
+            .returning(|pid, _| {
+                Ok(Process {
+                    pid,
+                    ..Default::default()
+                })
+            });
+
+        let service = WitrService::new(mock).with_enrich(Enrich::NETWORK | Enrich::SERVICE);
+        assert!(service.inspect_pid(7).is_ok());
+    }
 }
@@ -1,4 +1,4 @@
-use chrono::{DateTime, TimeZone, Utc};
+use chrono::{DateTime, Local, TimeZone, Utc};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 pub fn format_duration(start_time: u64) -> (String, String) {
@@ -29,6 +29,37 @@ pub fn format_duration(start_time: u64) -> (String, String) {
     (relative, formatted)
 }
 
+/// Like [`format_duration`], for a time still to come such as a cron job's
+/// next run: `in 5 hours`, and the time in the local zone its schedule is
+/// written in.
+pub fn format_until(time: u64) -> (String, String) {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    let duration_secs = time.saturating_sub(now);
+
+    let relative = match duration_secs {
+        0..=59 => "in under a minute".to_string(),
+        60..=119 => "in 1 min".to_string(),
+        120..=3599 => format!("in {} min", duration_secs / 60),
+        3600..=7199 => "in 1 hour".to_string(),
+        7200..=86399 => format!("in {} hours", duration_secs / 3600),
+        86400..=172799 => "in 1 day".to_string(),
+        _ => format!("in {} days", duration_secs / 86400),
+    };
+
+    let datetime: DateTime<Local> = Local
+        .timestamp_opt(time as i64, 0)
+        .single()
+        .unwrap_or_else(Local::now);
+
+    let formatted = datetime.format("%a %Y-%m-%d %H:%M:%S %z").to_string();
+
+    (relative, formatted)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -79,4 +110,22 @@ mod tests {
         let (relative, _) = format_duration(three_days_ago);
         assert_eq!(relative, "3 days ago");
     }
+
+    #[test]
+    fn test_format_until() {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        let in_five_hours = now + 5 * 3600 + 30;
+        let (relative, absolute) = format_until(in_five_hours);
+        assert_eq!(relative, "in 5 hours");
+        let local = Local.timestamp_opt(in_five_hours as i64, 0).unwrap();
+        assert_eq!(
+            absolute,
+            local.format("%a %Y-%m-%d %H:%M:%S %z").to_string()
+        );
+        assert_eq!(format_until(now + 3 * 86400 + 30).0, "in 3 days");
+    }
 }
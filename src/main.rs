@@ -1,52 +1,94 @@
+mod args;
+mod report;
+
 use anyhow::Result;
+use args::{Args, Command};
 use clap::Parser;
+use report::{print_chain, print_holders, print_owners, target_processes};
+use std::path::PathBuf;
 use witr_rs::adapters::system::RealSystem;
 use witr_rs::core::color::ColorScheme;
+use witr_rs::core::models::{Enrich, PathQuery};
+use witr_rs::core::ports::SystemProvider;
 use witr_rs::core::service::WitrService;
 use witr_rs::output;
 
-#[derive(Parser, Debug)]
-#[command(name = "witr-rs", version, about = "Why is this running? (Rust edition)", long_about = None)]
-struct Args {
-    name: Option<String>,
+fn main() -> Result<()> {
+    let args = Args::parse();
 
-    #[arg(short = 'p', long)]
-    pid: Option<u32>,
+    if let Some(Command::Capture { output, with_env }) = &args.command {
+        return capture(&args, output, *with_env);
+    }
 
-    #[arg(short = 'P', long)]
-    port: Option<u16>,
+    let enrich = if let Some(fields) = args.fields {
+        fields
+    } else if args.short || args.tree {
+        Enrich::empty()
+    } else if args.env {
+        Enrich::ENV
+    } else if args.files {
+        Enrich::FILES
+    } else if args.maps {
+        Enrich::MEMORY | Enrich::ENV
+    } else if args.warnings {
+        Enrich::NETWORK | Enrich::FILES | Enrich::MEMORY | Enrich::ENV
+    } else if args.security_scan {
+        Enrich::NETWORK
+            | Enrich::SERVICE
+            | Enrich::CONTAINER
+            | Enrich::FILES
+            | Enrich::MEMORY
+            | Enrich::ENV
+            | Enrich::CRON
+            | Enrich::SUPERVISOR
+            | Enrich::INIT
+    } else {
+        Enrich::all()
+    };
 
-    #[arg(long)]
-    short: bool,
+    if let Some(root) = &args.fixture {
+        return run_fixture(&args, root, enrich);
+    }
 
-    #[arg(long)]
-    tree: bool,
+    run(&args, WitrService::new(system(&args)).with_enrich(enrich))
+}
 
-    #[arg(long)]
-    json: bool,
+#[cfg(target_os = "linux")]
+fn capture(args: &Args, output: &std::path::Path, with_env: bool) -> Result<()> {
+    use witr_rs::adapters::capture;
 
-    #[arg(long)]
-    warnings: bool,
+    let (procfs, host) = args.roots();
+    let prefix = output.file_stem().map(PathBuf::from).unwrap_or_default();
+    let file = std::io::BufWriter::new(std::fs::File::create(output)?);
+    let count = capture::capture(&procfs, &host, &prefix, with_env, file)?;
+    println!("Captured {} processes to {}", count, output.display());
+    Ok(())
+}
 
-    #[arg(long)]
-    no_color: bool,
+#[cfg(not(target_os = "linux"))]
+fn capture(_args: &Args, _output: &std::path::Path, _with_env: bool) -> Result<()> {
+    anyhow::bail!("capture is only supported on Linux")
+}
 
-    #[arg(long)]
-    env: bool,
+fn system(args: &Args) -> RealSystem {
+    let (procfs, host) = args.roots();
+    RealSystem::with_roots(procfs, host)
+}
 
-    #[arg(long, aliases = ["sec", "scan"])]
-    security_scan: bool,
+#[cfg(target_os = "linux")]
+fn run_fixture(args: &Args, root: &std::path::Path, enrich: Enrich) -> Result<()> {
+    let sys = witr_rs::adapters::FixtureSystem::load(root)?;
+    run(args, WitrService::new(sys).with_enrich(enrich))
+}
 
-    #[arg(long, help = "Live watch mode")]
-    watch: bool,
+#[cfg(not(target_os = "linux"))]
+fn run_fixture(_args: &Args, _root: &std::path::Path, _enrich: Enrich) -> Result<()> {
+    anyhow::bail!("--fixture is only supported on Linux")
 }
 
-fn main() -> Result<()> {
-    let args = Args::parse();
+fn run<S: SystemProvider>(args: &Args, service: WitrService<S>) -> Result<()> {
     let colors = ColorScheme::new(!args.no_color);
-
-    let sys_adapter = RealSystem::new();
-    let service = WitrService::new(sys_adapter);
+    let service = service.with_thresholds(args.thresholds());
 
     if args.watch {
         let mut target_pid = args.pid;
@@ -59,25 +101,20 @@ fn main() -> Result<()> {
                     }
                 }
             } else if let Some(port) = args.port {
-                if let Ok(p) = service.inspect_port(port) {
-                    target_pid = Some(p.pid);
+                if let Ok(owners) = service.inspect_port(port) {
+                    target_pid = owners.first().map(|o| o.process.pid);
+                }
+            } else if let Some(remote) = &args.remote {
+                if let Ok(owners) = service.inspect_remote(remote) {
+                    target_pid = owners.first().map(|o| o.process.pid);
                 }
             }
         }
 
-        if let Some(pid) = target_pid {
-            if let Err(e) = witr_rs::tui::run::run_tui(Some(pid)) {
-                eprintln!("Error running TUI: {}", e);
-            }
-            return Ok(());
-        } else {
-            eprintln!(
-                "Global Watch Mode is currently disabled/WIP (Performance Optimization pending)."
-            );
-            eprintln!("Please specify a process target (PID, Name, or Port).");
-            eprintln!("Usage: witr-rs <NAME> --watch OR witr-rs -p <PID> --watch");
-            return Ok(());
+        if let Err(e) = witr_rs::tui::run::run_tui(target_pid, service) {
+            eprintln!("Error running TUI: {}", e);
         }
+        return Ok(());
     }
 
     if args.env {
@@ -90,6 +127,18 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
+    if args.files || args.maps {
+        match target_processes(args, &service) {
+            Some(Ok(processes)) => processes.iter().for_each(|p| match args.files {
+                true => output::files::print(p, &colors),
+                false => output::maps::print(p, &colors),
+            }),
+            Some(Err(e)) => eprintln!("Error: {}", e),
+            None => missing_target(),
+        }
+        return Ok(());
+    }
+
     if args.security_scan {
         let targets = if let Some(pid) = args.pid {
             match service.get_inspection(pid) {
@@ -102,13 +151,8 @@ fn main() -> Result<()> {
         } else if let Some(name) = &args.name {
             match service.inspect_name(name) {
                 Ok(procs) => {
-                    let mut results = Vec::new();
-                    for p in procs {
-                        if let Ok(res) = service.get_inspection(p.pid) {
-                            results.push(res);
-                        }
-                    }
-                    results
+                    let pids: Vec<u32> = procs.iter().map(|p| p.pid).collect();
+                    service.inspect_many(&pids)
                 }
                 Err(e) => {
                     eprintln!("Error inspecting name {}: {}", name, e);
@@ -117,18 +161,26 @@ fn main() -> Result<()> {
             }
         } else if let Some(port) = args.port {
             match service.inspect_port(port) {
-                Ok(p) => match service.get_inspection(p.pid) {
-                    Ok(r) => vec![r],
-                    Err(e) => {
-                        eprintln!("Error getting inspection for PID {}: {}", p.pid, e);
-                        return Ok(());
-                    }
-                },
+                Ok(owners) => {
+                    let pids: Vec<u32> = owners.iter().map(|o| o.process.pid).collect();
+                    service.inspect_many(&pids)
+                }
                 Err(e) => {
                     eprintln!("Error inspecting port {}: {}", port, e);
                     return Ok(());
                 }
             }
+        } else if let Some(remote) = &args.remote {
+            match service.inspect_remote(remote) {
+                Ok(owners) => {
+                    let pids: Vec<u32> = owners.iter().map(|o| o.process.pid).collect();
+                    service.inspect_many(&pids)
+                }
+                Err(e) => {
+                    eprintln!("Error inspecting remote {}: {}", remote, e);
+                    return Ok(());
+                }
+            }
         } else {
             println!("Scanning all processes for security issues... (this may take a moment)");
             match service.inspect_all() {
@@ -148,24 +200,12 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
-    if let Some(name) = args.name {
-        match service.inspect_name(&name) {
+    if let Some(name) = &args.name {
+        match service.inspect_name(name) {
             Ok(processes) => {
                 for process in processes {
                     match service.get_ancestry(process.pid) {
-                        Ok(chain) => {
-                            if args.short {
-                                output::short::print(&chain, &colors);
-                            } else if args.tree {
-                                output::tree::print(&chain, 0);
-                            } else if args.json {
-                                let _ = output::json::print(&process, &chain);
-                            } else if args.warnings {
-                                output::warnings::print(&chain);
-                            } else {
-                                output::standard::print(&process, &chain, &colors);
-                            }
-                        }
+                        Ok(chain) => print_chain(args, &process, &chain, &colors),
                         Err(e) => eprintln!("Error: {}", e),
                     }
                 }
@@ -176,17 +216,7 @@ fn main() -> Result<()> {
         match service.get_ancestry(pid) {
             Ok(chain) => {
                 if let Some(target) = chain.last() {
-                    if args.short {
-                        output::short::print(&chain, &colors);
-                    } else if args.tree {
-                        output::tree::print(&chain, 0);
-                    } else if args.json {
-                        let _ = output::json::print(target, &chain);
-                    } else if args.warnings {
-                        output::warnings::print(&chain);
-                    } else {
-                        output::standard::print(target, &chain, &colors);
-                    }
+                    print_chain(args, target, &chain, &colors);
                 } else {
                     eprintln!("Error: Process {} not found", pid);
                 }
@@ -195,28 +225,47 @@ fn main() -> Result<()> {
         }
     } else if let Some(port) = args.port {
         match service.inspect_port(port) {
-            Ok(process) => match service.get_ancestry(process.pid) {
-                Ok(chain) => {
-                    if args.short {
-                        output::short::print(&chain, &colors);
-                    } else if args.tree {
-                        output::tree::print(&chain, 0);
-                    } else if args.json {
-                        let _ = output::json::print(&process, &chain);
-                    } else if args.warnings {
-                        output::warnings::print(&chain);
-                    } else {
-                        output::standard::print(&process, &chain, &colors);
-                    }
-                }
-                Err(e) => eprintln!("Error: {}", e),
-            },
+            Ok(owners) => print_owners(
+                args,
+                &service,
+                "Port        ",
+                &port.to_string(),
+                &owners,
+                &colors,
+            ),
+            Err(e) => eprintln!("Error: {}", e),
+        }
+    } else if let Some(remote) = &args.remote {
+        match service.inspect_remote(remote) {
+            Ok(owners) => print_owners(
+                args,
+                &service,
+                "Remote      ",
+                &remote.to_string(),
+                &owners,
+                &colors,
+            ),
+            Err(e) => eprintln!("Error: {}", e),
+        }
+    } else if let Some(query) = args.path_query() {
+        let label = match query {
+            PathQuery::File(_) => "File        ",
+            PathQuery::Under(_) => "Mount       ",
+        };
+        match service.inspect_path(&query) {
+            Ok(holders) => {
+                print_holders(args, &service, label, &query.to_string(), &holders, &colors)
+            }
             Err(e) => eprintln!("Error: {}", e),
         }
     } else {
-        eprintln!("Please specify a process name, PID, or port to inspect");
-        std::process::exit(1);
+        missing_target();
     }
 
     Ok(())
 }
+
+fn missing_target() -> ! {
+    eprintln!("Please specify a process name, PID, port, remote endpoint or path to inspect");
+    std::process::exit(1);
+}
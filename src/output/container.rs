@@ -0,0 +1,96 @@
+use crate::core::color::ColorScheme;
+use crate::core::models::{ContainerInfo, ContainerState, PodInfo};
+use crate::core::time;
+
+/// The container and pod lines of the standard view.
+pub fn print(info: &ContainerInfo, colors: &ColorScheme) {
+    print!("{}   : {}", colors.metadata("Container"), info.runtime);
+    if let Some(name) = &info.name {
+        print!(" {}", name);
+    }
+    print!(" {}", colors.dim(&format!("({})", info.short_id)));
+    if let Some(image) = &info.image {
+        print!(" image {}", image);
+    }
+    println!();
+    if let Some(state) = &info.state {
+        print_state(state, colors);
+    }
+    if let Some(uid) = &info.pod_uid {
+        print_pod(info.pod.as_ref(), uid, info.qos_class.as_deref(), colors);
+    }
+}
+
+fn print_pod(pod: Option<&PodInfo>, uid: &str, qos: Option<&str>, colors: &ColorScheme) {
+    let name = pod.and_then(|p| {
+        let name = p.name.as_ref()?;
+        Some(match &p.namespace {
+            Some(ns) => format!("{}/{}", ns, name),
+            None => name.clone(),
+        })
+    });
+    print!(
+        "{}         : {}",
+        colors.metadata("Pod"),
+        name.as_deref().unwrap_or(uid)
+    );
+    if let Some(container) = pod.and_then(|p| p.container.as_ref()) {
+        print!(" container {}", container);
+    }
+    if let Some(qos) = qos {
+        print!(" {}", colors.dim(&format!("({})", qos)));
+    }
+    if let Some(restarts) = pod.and_then(|p| p.restart_count).filter(|&r| r > 0) {
+        print!(" {}", colors.warning(&format!("(Restarts: {})", restarts)));
+    }
+    println!();
+    if let Some(owner) = pod.and_then(|p| p.owner.as_ref()) {
+        print!(
+            "{}    : {} {}",
+            colors.metadata("Workload"),
+            owner.kind,
+            owner.name
+        );
+        if owner.inferred {
+            print!(" {}", colors.dim("(inferred from pod name)"));
+        }
+        println!();
+    }
+}
+
+fn print_state(state: &ContainerState, colors: &ColorScheme) {
+    print!("{}       : {}", colors.metadata("State"), state.status);
+    if let Some(started) = state.started_at {
+        print!(" since {}", time::format_duration(started).0);
+    }
+    match state.health.as_deref() {
+        Some("unhealthy") => print!(
+            " {}",
+            colors.warning(&format!(
+                "[unhealthy, {} failed checks]",
+                state.failing_streak
+            ))
+        ),
+        Some(health) => print!(" {}", colors.badge(&format!("[{}]", health))),
+        None => {}
+    }
+    if state.restart_count > 0 {
+        print!(
+            " {}",
+            colors.warning(&format!("(Restarts: {})", state.restart_count))
+        );
+    }
+    println!();
+    if let Some(exit) = &state.last_exit {
+        print!(
+            "{}   : code {} {}",
+            colors.metadata("Last Exit"),
+            exit.code,
+            time::format_duration(exit.finished_at).0
+        );
+        if exit.oom_killed {
+            print!(" {}", colors.warning("(OOM killed)"));
+        }
+        println!();
+    }
+}
@@ -0,0 +1,22 @@
+use crate::core::color::ColorScheme;
+use crate::core::models::CronJob;
+use crate::core::time;
+
+/// `Cron        : /etc/cron.d/backup:3 (0 2 * * *)`, with the job's user
+/// and next run.
+pub fn print(job: &CronJob, colors: &ColorScheme) {
+    print!("{}        : {}", colors.metadata("Cron"), job);
+    if let Some(user) = &job.user {
+        print!(" {}", colors.dim(&format!("as {}", user)));
+    }
+    println!();
+    if let Some(next) = job.next_run {
+        let (relative, absolute) = time::format_until(next);
+        println!(
+            "{}    : {} {}",
+            colors.metadata("Next Run"),
+            relative,
+            colors.dim(&format!("({})", absolute))
+        );
+    }
+}
@@ -0,0 +1,57 @@
+use crate::core::color::ColorScheme;
+use crate::core::models::{FileKind, Process};
+
+pub fn print(process: &Process, colors: &ColorScheme) {
+    println!(
+        "{}      : {} {}",
+        colors.header("Target"),
+        process.name,
+        colors.dim(&format!("(pid {})", process.pid))
+    );
+    let Some(files) = &process.files else {
+        println!("Open files are not readable for this process");
+        return;
+    };
+
+    let limit = match files.file_limit {
+        0 => String::new(),
+        limit => format!(" of {}", limit),
+    };
+    println!(
+        "{}  : {}{}",
+        colors.metadata("Open Files"),
+        files.open_files,
+        limit
+    );
+    println!();
+
+    for file in &files.fds {
+        let pos = match file.kind {
+            FileKind::File => colors.dim(&format!(" @{}", file.pos)),
+            _ => String::new(),
+        };
+        println!(
+            "{:>5} {:<2} {:<8} {}{}",
+            file.fd,
+            file.mode(),
+            file.kind.to_string(),
+            file.target,
+            pos
+        );
+    }
+
+    if !files.locked_files.is_empty() {
+        println!();
+        println!("{}:", colors.command("Locked"));
+        for lock in &files.locked_files {
+            println!("  • {}", lock);
+        }
+    }
+    if !files.watched_dirs.is_empty() {
+        println!();
+        println!("{}:", colors.command("Watching"));
+        for dir in &files.watched_dirs {
+            println!("  • {}", dir);
+        }
+    }
+}
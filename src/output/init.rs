@@ -0,0 +1,13 @@
+use crate::core::color::ColorScheme;
+use crate::core::models::InitService;
+
+/// `Init Service: runit redis (/etc/service/redis/run)`, then what the
+/// manager does when the process exits.
+pub fn print(service: &InitService, colors: &ColorScheme) {
+    println!("{}: {}", colors.metadata("Init Service"), service);
+    print!("{}     : {}", colors.metadata("Restart"), service.restart);
+    if let Some(pidfile) = &service.pidfile {
+        print!(" {}", colors.dim(&format!("(pid file {})", pidfile)));
+    }
+    println!();
+}
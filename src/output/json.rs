@@ -1,4 +1,4 @@
-use crate::core::models::Process;
+use crate::core::models::{Process, SocketOwner};
 use serde_json;
 use std::io::{self, Write};
 
@@ -20,6 +20,56 @@ pub fn print(target: &Process, chain: &[Process]) -> Result<(), Box<dyn std::err
     print_to_writer(&mut handle, target, chain)
 }
 
+/// Every owner of a port or remote endpoint as one document: the sockets
+/// each holds, its ancestry (`chains`, in the same order) and the ancestor
+/// they share.
+pub fn print_owners_to_writer<W: Write>(
+    writer: &mut W,
+    query: &str,
+    owners: &[SocketOwner],
+    chains: &[Vec<Process>],
+    shared: Option<&Process>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let owners: Vec<_> = owners
+        .iter()
+        .zip(chains)
+        .map(|(owner, chain)| {
+            // The family is derived from the address, so it is not a field.
+            let sockets: Vec<_> = owner
+                .sockets
+                .iter()
+                .map(|socket| {
+                    let mut value = serde_json::json!(socket);
+                    value["family"] = socket.family().into();
+                    value
+                })
+                .collect();
+            serde_json::json!({
+                "process": owner.process,
+                "sockets": sockets,
+                "ancestry": chain,
+            })
+        })
+        .collect();
+    let output = serde_json::json!({
+        "query": query,
+        "owners": owners,
+        "shared_ancestor": shared,
+    });
+    writeln!(writer, "{}", serde_json::to_string_pretty(&output)?)?;
+    Ok(())
+}
+
+pub fn print_owners(
+    query: &str,
+    owners: &[SocketOwner],
+    chains: &[Vec<Process>],
+    shared: Option<&Process>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut handle = io::stdout().lock();
+    print_owners_to_writer(&mut handle, query, owners, chains, shared)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -37,4 +87,29 @@ mod tests {
         assert!(output.contains("\"target\""));
         assert!(output.contains("\"ancestry\""));
     }
+
+    #[test]
+    fn test_print_owners_json() {
+        let owner = |pid| SocketOwner {
+            process: Process {
+                pid,
+                ..Default::default()
+            },
+            sockets: vec![Default::default()],
+        };
+        let owners = [owner(812), owner(813)];
+        let chains = vec![
+            vec![owners[0].process.clone()],
+            vec![owners[0].process.clone(), owners[1].process.clone()],
+        ];
+        let mut buffer = Vec::new();
+        print_owners_to_writer(&mut buffer, "80", &owners, &chains, chains[1].first()).unwrap();
+
+        let output: serde_json::Value = serde_json::from_slice(&buffer).unwrap();
+        assert_eq!(output["query"], "80");
+        assert_eq!(output["owners"][1]["process"]["pid"], 813);
+        assert!(output["owners"][1]["sockets"][0]["family"].is_string());
+        assert_eq!(output["owners"][1]["ancestry"].as_array().unwrap().len(), 2);
+        assert_eq!(output["shared_ancestor"]["pid"], 812);
+    }
 }
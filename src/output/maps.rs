@@ -0,0 +1,58 @@
+use crate::core::color::ColorScheme;
+use crate::core::models::Process;
+
+pub fn print(process: &Process, colors: &ColorScheme) {
+    println!(
+        "{}      : {} {}",
+        colors.header("Target"),
+        process.name,
+        colors.dim(&format!("(pid {})", process.pid))
+    );
+    let Some(memory) = &process.memory else {
+        println!("Memory maps are not readable for this process");
+        return;
+    };
+
+    let mb = |bytes: u64| format!("{:.1} MB", bytes as f64 / 1024.0 / 1024.0);
+    println!(
+        "{}      : RSS {}, PSS {} (anon {}, file {}), swap {}",
+        colors.metadata("Memory"),
+        mb(memory.rss),
+        mb(memory.pss),
+        mb(memory.pss_anon),
+        mb(memory.pss_file),
+        mb(memory.swap)
+    );
+    println!(
+        "              shared {}, private {}",
+        mb(memory.shared),
+        mb(memory.private)
+    );
+    if let Some(libs) = process.ld_preload() {
+        println!("{}  : {}", colors.warning("LD_PRELOAD"), libs);
+    }
+
+    if !memory.libraries.is_empty() {
+        println!();
+        println!("{}:", colors.command("Libraries"));
+        for lib in &memory.libraries {
+            println!("  • {}", lib);
+        }
+    }
+    if !memory.anon_exec.is_empty() {
+        println!();
+        println!("{}:", colors.command("Anonymous Executable"));
+        for range in &memory.anon_exec {
+            println!("  • {}", range);
+        }
+    }
+
+    let warnings = memory.warnings();
+    if !warnings.is_empty() {
+        println!();
+        println!("{}:", colors.warning("Warnings"));
+        for warning in &warnings {
+            println!("  • {}", warning);
+        }
+    }
+}
@@ -1,7 +1,15 @@
+pub mod container;
+pub mod cron;
 pub mod envonly;
+pub mod files;
+pub mod init;
 pub mod json;
+pub mod maps;
+pub mod owners;
 pub mod security;
 pub mod short;
 pub mod standard;
+pub mod supervisor;
 pub mod tree;
+pub mod unit;
 pub mod warnings;
@@ -0,0 +1,86 @@
+use crate::core::color::ColorScheme;
+use crate::core::models::{PathHolder, Process, SocketOwner};
+
+/// Summary printed ahead of the per-process reports when several processes
+/// match a port or remote endpoint. `label` is padded like the other report
+/// headers, e.g. `Port        `.
+pub fn print(
+    label: &str,
+    query: &str,
+    owners: &[SocketOwner],
+    shared: Option<&Process>,
+    colors: &ColorScheme,
+) {
+    println!(
+        "{}: {} {}",
+        colors.header(label),
+        query,
+        colors.dim(&format!("({} processes)", owners.len()))
+    );
+    for owner in owners {
+        let p = &owner.process;
+        for socket in &owner.sockets {
+            let peer = if socket.is_listening() {
+                String::new()
+            } else {
+                format!(" → {}", socket.remote_addr)
+            };
+            println!(
+                "  {} {}  {} {} {}{} ({})",
+                p.name,
+                colors.dim(&format!("(pid {})", p.pid)),
+                socket.protocol,
+                socket.family(),
+                socket.local_addr,
+                peer,
+                socket.state
+            );
+        }
+    }
+    print_shared(shared, colors);
+}
+
+/// Summary printed ahead of the per-process reports for `--file` and
+/// `--mount`, listing how each process holds the matching paths.
+pub fn print_paths(
+    label: &str,
+    query: &str,
+    holders: &[PathHolder],
+    shared: Option<&Process>,
+    colors: &ColorScheme,
+) {
+    println!(
+        "{}: {} {}",
+        colors.header(label),
+        query,
+        colors.dim(&match holders.len() {
+            1 => "(1 process)".to_string(),
+            n => format!("({} processes)", n),
+        })
+    );
+    for holder in holders {
+        let p = &holder.process;
+        for held in &holder.paths {
+            println!(
+                "  {} {}  {:<6} {}",
+                p.name,
+                colors.dim(&format!("(pid {})", p.pid)),
+                held.usage.to_string(),
+                held.path
+            );
+        }
+    }
+    print_shared(shared, colors);
+}
+
+fn print_shared(shared: Option<&Process>, colors: &ColorScheme) {
+    if let Some(ancestor) = shared {
+        println!(
+            "{}: {} {}",
+            colors.metadata("Shared Ancestor"),
+            ancestor.name,
+            colors.dim(&format!("(pid {})", ancestor.pid))
+        );
+    }
+    println!();
+}
@@ -22,6 +22,7 @@ pub fn print_report(results: &[InspectionResult], colors: &ColorScheme) {
                 .contains("CRITICAL")
                 || warning.contains("REVERSE SHELL")
                 || warning.contains("BINARY DELETED")
+                || warning.contains("INJECTED LIBRARY")
             {
                 critical_count += 1;
                 ("CRITICAL", |s| s.red().bold())
@@ -1,8 +1,9 @@
+use super::{container, cron, init, supervisor, unit};
 use crate::core::color::ColorScheme;
-use crate::core::models::Process;
+use crate::core::models::{CgroupInfo, Process, ResourceContext, Thresholds};
 use crate::core::time;
 
-pub fn print(target: &Process, chain: &[Process], colors: &ColorScheme) {
+pub fn print(target: &Process, chain: &[Process], thresholds: &Thresholds, colors: &ColorScheme) {
     println!("{}      : {}", colors.header("Target"), target.name);
     println!();
 
@@ -25,21 +26,21 @@ pub fn print(target: &Process, chain: &[Process], colors: &ColorScheme) {
         println!("{}        : {}", colors.metadata("User"), uid);
     }
 
-    if let Some(container) = &target.container {
+    if let Some(info) = &target.container_info {
+        container::print(info, colors);
+    } else if let Some(container) = &target.container {
         println!("{}   : {}", colors.metadata("Container"), container);
     }
 
-    if let Some(service) = &target.service {
-        print!("{}     : {}", colors.metadata("Service"), service);
-        if let Some(restarts) = target.restart_count {
-            if restarts > 0 {
-                print!(" {}", colors.warning(&format!("(Restarts: {})", restarts)));
-            }
-        }
-        println!();
-        if let Some(path) = &target.service_file {
-            println!("{}   : {}", colors.metadata("Unit File"), path);
-        }
+    unit::print(target, colors);
+    if let Some(job) = &target.cron {
+        cron::print(job, colors);
+    }
+    if let Some(program) = &target.supervisor {
+        supervisor::print(program, colors);
+    }
+    if let Some(service) = &target.init {
+        init::print(service, colors);
     }
 
     if !target.cmd.is_empty() {
@@ -65,13 +66,24 @@ pub fn print(target: &Process, chain: &[Process], colors: &ColorScheme) {
         .map(|p| format!("{} {}", p.name, colors.dim(&format!("(pid {})", p.pid))))
         .collect();
     println!("{}", names.join(" → "));
+    unit::print_trigger(target, colors);
+    if let Some(cgroup) = &target.cgroup {
+        print_cgroup(cgroup, colors);
+    }
     println!();
 
-    let source_label = if let Some(name) = chain
-        .last()
-        .and_then(|p| p.service.as_ref().or(p.container.as_ref()))
-    {
-        name.clone()
+    let manager = target
+        .cron
+        .as_ref()
+        .map(|job| format!("cron {}", job.location()))
+        .or_else(|| Some(format!("supervisor {}", target.supervisor.as_ref()?)))
+        .or_else(|| Some(target.init.as_ref()?.to_string()));
+    let source_label = if let Some(name) = manager.or_else(|| {
+        chain
+            .last()
+            .and_then(|p| p.service.clone().or_else(|| p.container_source()))
+    }) {
+        name
     } else if target.parent_pid == Some(1) || target.parent_pid.is_none() {
         "system".to_string()
     } else {
@@ -104,11 +116,14 @@ pub fn print(target: &Process, chain: &[Process], colors: &ColorScheme) {
                 .map(|s| s.as_str())
                 .unwrap_or("UNKNOWN");
 
-            let state_info = if state == "UNKNOWN" {
+            let mut state_info = if state == "UNKNOWN" {
                 String::new()
             } else {
                 format!(" ({})", state)
             };
+            if let Some(ns) = target.netns() {
+                state_info.push_str(&colors.dim(&format!(" netns: {}", ns)));
+            }
 
             if i == 0 {
                 println!(
@@ -123,9 +138,24 @@ pub fn print(target: &Process, chain: &[Process], colors: &ColorScheme) {
             }
         }
     }
+    for (i, socket) in target.unix_sockets().iter().enumerate() {
+        let path = socket.unix_path.as_deref().unwrap_or_default();
+        if i == 0 {
+            print!("{}: ", colors.command("Unix Sockets"));
+        } else {
+            print!("              ");
+        }
+        println!("{} ({})", path, socket.state);
+    }
 
+    if let Some(resources) = &target.resources {
+        print_resources(resources, colors);
+    }
+
+    let context_warnings = target.context_warnings(thresholds);
     if target.health != "healthy"
         || target.uid == Some("0".to_string())
+        || !context_warnings.is_empty()
         || target
             .bind_addrs
             .iter()
@@ -144,5 +174,107 @@ pub fn print(target: &Process, chain: &[Process], colors: &ColorScheme) {
                 println!("  • Listening publicly on {}:{}", addr, port);
             }
         }
+        for warning in &context_warnings {
+            println!("  • {}", warning);
+        }
+    }
+}
+
+fn print_resources(res: &ResourceContext, colors: &ColorScheme) {
+    let mb = |bytes: u64| format!("{:.1} MB", bytes as f64 / 1024.0 / 1024.0);
+    if let Some(current) = res.memory_current {
+        let limit = match res.memory_max {
+            Some(max) => format!(" of {} limit", mb(max)),
+            None => String::new(),
+        };
+        println!(
+            "{}  : {}{}",
+            colors.metadata("Memory Use"),
+            mb(current),
+            limit
+        );
+    }
+    if let Some(cpus) = res.cpu_max {
+        let throttled = match res.cpu_throttling {
+            Some(t) if t.throttled > 0 => {
+                format!(", throttled in {} of {} periods", t.throttled, t.periods)
+            }
+            _ => String::new(),
+        };
+        println!(
+            "{}   : {:.2} CPUs{}",
+            colors.metadata("CPU Limit"),
+            cpus,
+            throttled
+        );
+    }
+    if !res.pressure.is_empty() {
+        let stalls: Vec<String> = res
+            .pressure
+            .iter()
+            .map(|p| format!("{} {:.2}%", p.resource, p.some))
+            .collect();
+        println!(
+            "{}    : {} {}",
+            colors.metadata("Pressure"),
+            stalls.join(", "),
+            colors.dim("(avg10)")
+        );
+    }
+    if let Some(score) = res.oom_score {
+        println!(
+            "{}   : {} {}",
+            colors.metadata("OOM Score"),
+            score,
+            colors.dim(&format!("(adj {})", res.oom_score_adj.unwrap_or(0)))
+        );
+    }
+    for inhibitor in &res.inhibitors {
+        println!("{}    : {}", colors.warning("Inhibits"), inhibitor);
+    }
+}
+
+// The cgroup path names the unit, session or container that owns the
+// process even when that unit did not start it directly.
+fn print_cgroup(cgroup: &CgroupInfo, colors: &ColorScheme) {
+    if cgroup.levels.is_empty() {
+        return;
+    }
+    let levels: Vec<String> = cgroup
+        .levels
+        .iter()
+        .map(|level| {
+            let mut limits = Vec::new();
+            if let Some(max) = level.memory_max {
+                limits.push(format!("memory {:.1} MB", max as f64 / 1024.0 / 1024.0));
+            }
+            if let Some(cpus) = level.cpu_max {
+                limits.push(format!("cpu {:.2}", cpus));
+            }
+            if let Some(pids) = level.pids_max {
+                limits.push(format!("pids {}", pids));
+            }
+            match limits.is_empty() {
+                true => level.name.clone(),
+                false => format!(
+                    "{} {}",
+                    level.name,
+                    colors.dim(&format!("[{}]", limits.join(", ")))
+                ),
+            }
+        })
+        .collect();
+    println!(
+        "{}      : {}",
+        colors.metadata("Cgroup"),
+        levels.join(" → ")
+    );
+    if let Some(owner) = cgroup.owner() {
+        println!(
+            "{}    : {} {}",
+            colors.metadata("Owned By"),
+            owner.name,
+            colors.dim(&format!("({})", owner.kind))
+        );
     }
 }
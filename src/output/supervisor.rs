@@ -0,0 +1,31 @@
+use crate::core::color::ColorScheme;
+use crate::core::models::SupervisorProgram;
+use crate::core::time;
+
+/// The supervisord program lines of the standard view: the program and its
+/// config, then what supervisord reports about it.
+pub fn print(program: &SupervisorProgram, colors: &ColorScheme) {
+    println!("{}  : {}", colors.metadata("Supervisor"), program);
+    let mut lifecycle = Vec::new();
+    if let Some(state) = &program.state {
+        lifecycle.push(state.state.clone());
+        if let Some(started) = state.started_at {
+            lifecycle.push(format!("started {}", time::format_duration(started).0));
+        }
+        if let Some(code) = state.exit_status.filter(|code| *code != 0) {
+            lifecycle.push(format!("last exit {}", code));
+        }
+        if let Some(error) = &state.spawn_error {
+            lifecycle.push(colors.warning(error));
+        }
+    }
+    lifecycle.push(format!("autorestart={}", program.autorestart));
+    if program.numprocs > 1 {
+        lifecycle.push(format!("numprocs={}", program.numprocs));
+    }
+    println!(
+        "{}   : {}",
+        colors.metadata("Lifecycle"),
+        lifecycle.join(", ")
+    );
+}
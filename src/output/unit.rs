@@ -0,0 +1,66 @@
+use crate::core::color::ColorScheme;
+use crate::core::models::{Process, SystemdUnit};
+use crate::core::time;
+
+/// The service or other systemd unit lines of the standard view.
+pub fn print(target: &Process, colors: &ColorScheme) {
+    if let Some(service) = &target.service {
+        print!("{}     : {}", colors.metadata("Service"), service);
+        if let Some(uid) = target.unit.as_ref().and_then(|u| u.user) {
+            print!(" {}", colors.dim(&format!("(user {})", uid)));
+        }
+        if let Some(restarts) = target.restart_count {
+            if restarts > 0 {
+                print!(" {}", colors.warning(&format!("(Restarts: {})", restarts)));
+            }
+        }
+        println!();
+        if let Some(path) = &target.service_file {
+            println!("{}   : {}", colors.metadata("Unit File"), path);
+        }
+    } else if let Some(unit) = &target.unit {
+        println!("{}        : {}", colors.metadata("Unit"), unit.name);
+    }
+    if let Some(unit) = &target.unit {
+        print_properties(unit, colors);
+    }
+}
+
+fn print_properties(unit: &SystemdUnit, colors: &ColorScheme) {
+    if let Some(exec) = &unit.exec_start {
+        println!("{}   : {}", colors.metadata("ExecStart"), exec);
+    }
+    let mut state = Vec::new();
+    if let Some(since) = unit.active_since {
+        state.push(format!("active since {}", time::format_duration(since).0));
+    }
+    if let Some(policy) = &unit.restart_policy {
+        state.push(format!("Restart={}", policy));
+    }
+    if !state.is_empty() {
+        println!("{}   : {}", colors.metadata("Lifecycle"), state.join(", "));
+    }
+}
+
+/// `nginx.service ← wanted by multi-user.target`, aligned under the
+/// Why It Exists chain.
+pub fn print_trigger(target: &Process, colors: &ColorScheme) {
+    let Some(unit) = &target.unit else {
+        return;
+    };
+    let Some(trigger) = &unit.trigger else {
+        return;
+    };
+    print!("{:15}{} ← {}", "", unit.name, trigger);
+    let mut times = Vec::new();
+    if let Some(last) = trigger.last {
+        times.push(format!("last {}", time::format_duration(last).0));
+    }
+    if let Some(next) = trigger.next {
+        times.push(format!("next {}", time::format_duration(next).1));
+    }
+    if !times.is_empty() {
+        print!(" {}", colors.dim(&format!("[{}]", times.join(", "))));
+    }
+    println!();
+}
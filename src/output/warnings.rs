@@ -1,6 +1,6 @@
-use crate::core::models::Process;
+use crate::core::models::{Process, Thresholds};
 
-pub fn print(chain: &[Process]) {
+pub fn print(chain: &[Process], thresholds: &Thresholds) {
     for p in chain {
         if p.health != "healthy" {
             println!("⚠  PID {} is {}", p.pid, p.health);
@@ -18,5 +18,8 @@ pub fn print(chain: &[Process]) {
                 }
             }
         }
+        for warning in p.context_warnings(thresholds) {
+            println!("⚠  PID {}: {}", p.pid, warning);
+        }
     }
 }
@@ -0,0 +1,101 @@
+use crate::args::Args;
+use witr_rs::core::ancestry::shared_ancestor;
+use witr_rs::core::color::ColorScheme;
+use witr_rs::core::models::{PathHolder, Process, SocketOwner};
+use witr_rs::core::ports::{SystemError, SystemProvider};
+use witr_rs::core::service::WitrService;
+use witr_rs::output;
+
+pub fn print_chain(args: &Args, target: &Process, chain: &[Process], colors: &ColorScheme) {
+    if args.short {
+        output::short::print(chain, colors);
+    } else if args.tree {
+        output::tree::print(chain, 0);
+    } else if args.json {
+        let _ = output::json::print(target, chain);
+    } else if args.warnings {
+        output::warnings::print(chain, &args.thresholds());
+    } else {
+        output::standard::print(target, chain, &args.thresholds(), colors);
+    }
+}
+
+pub fn print_owners<S: SystemProvider>(
+    args: &Args,
+    service: &WitrService<S>,
+    label: &str,
+    query: &str,
+    owners: &[SocketOwner],
+    colors: &ColorScheme,
+) {
+    let chains = ancestries(service, owners.iter().map(|o| &o.process));
+    if args.json {
+        let shared = shared_ancestor(&chains).filter(|_| owners.len() > 1);
+        let _ = output::json::print_owners(query, owners, &chains, shared);
+        return;
+    }
+    if is_standard(args) && owners.len() > 1 {
+        let shared = shared_ancestor(&chains);
+        output::owners::print(label, query, owners, shared, colors);
+    }
+    for (owner, chain) in owners.iter().zip(&chains) {
+        print_chain(args, &owner.process, chain, colors);
+    }
+}
+
+// How each process holds the path is the answer to the query, so the summary
+// is printed even for a single holder.
+pub fn print_holders<S: SystemProvider>(
+    args: &Args,
+    service: &WitrService<S>,
+    label: &str,
+    query: &str,
+    holders: &[PathHolder],
+    colors: &ColorScheme,
+) {
+    let chains = ancestries(service, holders.iter().map(|h| &h.process));
+    if is_standard(args) {
+        let shared = shared_ancestor(&chains).filter(|_| holders.len() > 1);
+        output::owners::print_paths(label, query, holders, shared, colors);
+    }
+    for (holder, chain) in holders.iter().zip(&chains) {
+        print_chain(args, &holder.process, chain, colors);
+    }
+}
+
+/// The processes the target arguments select, in the standard view's order
+/// of precedence: name, PID, port, remote endpoint, then path. `None` when
+/// no target was given.
+pub fn target_processes<S: SystemProvider>(
+    args: &Args,
+    service: &WitrService<S>,
+) -> Option<Result<Vec<Process>, SystemError>> {
+    let owned = |owners: Vec<SocketOwner>| owners.into_iter().map(|o| o.process).collect();
+    Some(if let Some(name) = &args.name {
+        service.inspect_name(name)
+    } else if let Some(pid) = args.pid {
+        service.inspect_pid(pid).map(|p| vec![p])
+    } else if let Some(port) = args.port {
+        service.inspect_port(port).map(owned)
+    } else if let Some(remote) = &args.remote {
+        service.inspect_remote(remote).map(owned)
+    } else {
+        let query = args.path_query()?;
+        service
+            .inspect_path(&query)
+            .map(|holders| holders.into_iter().map(|h| h.process).collect())
+    })
+}
+
+fn ancestries<'a, S: SystemProvider>(
+    service: &WitrService<S>,
+    processes: impl Iterator<Item = &'a Process>,
+) -> Vec<Vec<Process>> {
+    processes
+        .map(|p| service.get_ancestry(p.pid).unwrap_or_default())
+        .collect()
+}
+
+fn is_standard(args: &Args) -> bool {
+    !(args.short || args.tree || args.json || args.warnings)
+}
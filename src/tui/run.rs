@@ -1,4 +1,5 @@
-use crate::adapters::system::RealSystem;
+use crate::core::models::Enrich;
+use crate::core::ports::SystemProvider;
 use crate::core::service::WitrService;
 use crate::tui::app::App;
 use crate::tui::ui::ui;
@@ -13,7 +14,12 @@ use std::{
     time::{Duration, Instant},
 };
 
-pub fn run_tui(target_pid: Option<u32>) -> Result<(), Box<dyn std::error::Error>> {
+/// Watches `target_pid` through `service`'s provider, which is refreshed
+/// every tick.
+pub fn run_tui<S: SystemProvider>(
+    target_pid: Option<u32>,
+    service: WitrService<S>,
+) -> Result<(), Box<dyn std::error::Error>> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
@@ -21,8 +27,7 @@ pub fn run_tui(target_pid: Option<u32>) -> Result<(), Box<dyn std::error::Error>
     let mut terminal = Terminal::new(backend)?;
 
     let mut app = App::new(" Witr-RS Watch Mode ", target_pid);
-    let sys = RealSystem::new();
-    let service = WitrService::new(sys);
+    let service = service.with_enrich(Enrich::NETWORK | Enrich::SERVICE | Enrich::CONTAINER);
 
     let tick_rate = Duration::from_secs(1);
     let mut last_tick = Instant::now();
@@ -71,7 +76,8 @@ pub fn run_tui(target_pid: Option<u32>) -> Result<(), Box<dyn std::error::Error>
     Ok(())
 }
 
-fn refresh_data(app: &mut App, service: &WitrService<RealSystem>) {
+fn refresh_data<S: SystemProvider>(app: &mut App, service: &WitrService<S>) {
+    let _ = service.refresh();
     if let Some(pid) = app.target_pid {
         if let Ok(ancestry) = service.get_ancestry(pid) {
             app.set_data(ancestry);
@@ -84,22 +90,13 @@ fn refresh_data(app: &mut App, service: &WitrService<RealSystem>) {
         } else {
             app.title = format!(" Witr-RS Watch Mode (Process {} Lost) ", pid);
         }
-    } else if let Ok(_pids) = service.get_all_pids() {
-        let procs = Vec::new();
-        // TODO: Fetching all process details is resource intensive.
-        // We disabled this for now to optimize performance later.
-
-        /*
-        for pid in pids.iter().take(300) {
-                if let Ok(p) = service.inspect_pid(*pid) {
-                    procs.push(p);
-                }
-        }
-        procs.sort_by(|a, b| b.cpu_usage.partial_cmp(&a.cpu_usage).unwrap_or(std::cmp::Ordering::Equal));
-        */
-
-        // Placeholder to indicate Global Mode is active but limited
-        app.title = " Witr-RS Global Monitor (Detail Fetch Disabled - CPU Optimized) ".to_string();
-        app.set_data(procs); // Empty list for now
+    } else if let Ok(mut procs) = service.list_processes() {
+        procs.sort_by(|a, b| {
+            b.cpu_usage
+                .partial_cmp(&a.cpu_usage)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        app.title = format!(" Witr-RS Global Monitor ({} processes) ", procs.len());
+        app.set_data(procs);
     }
 }
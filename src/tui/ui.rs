@@ -231,8 +231,25 @@ fn draw_details(f: &mut Frame, p: &Process, area: Rect) {
             "Open Ports:",
             Style::default().add_modifier(Modifier::UNDERLINED),
         )));
+        let netns = p.netns().map(|ns| format!(" (netns: {})", ns));
         for port in &p.ports {
-            lines.push(Line::from(format!(" - {}", port)));
+            lines.push(Line::from(format!(
+                " - {}{}",
+                port,
+                netns.as_deref().unwrap_or_default()
+            )));
+        }
+    }
+    let unix_sockets = p.unix_sockets();
+    if !unix_sockets.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "Unix Sockets:",
+            Style::default().add_modifier(Modifier::UNDERLINED),
+        )));
+        for socket in unix_sockets {
+            let path = socket.unix_path.as_deref().unwrap_or_default();
+            lines.push(Line::from(format!(" - {} ({})", path, socket.state)));
         }
     }
 
@@ -0,0 +1,552 @@
+#![cfg(target_os = "linux")]
+
+use witr_rs::adapters::hostfs::HostFs;
+use witr_rs::adapters::proc::linux::socketstate::get_pid_socket_states;
+use witr_rs::adapters::proc::ProcFs;
+use witr_rs::adapters::source::{self, Systemctl};
+use witr_rs::adapters::system::RealSystem;
+use witr_rs::adapters::FixtureSystem;
+use witr_rs::core::ancestry::shared_ancestor;
+use witr_rs::core::models::{
+    CgroupKind, Enrich, FileKind, InitSystem, PathQuery, PathUse, RemoteFilter, SourceType,
+    Thresholds,
+};
+use witr_rs::core::service::WitrService;
+
+const ROOT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/nginx");
+// Containers whose PID 1 is the process manager itself.
+const CONTAINERS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/containers");
+
+fn service() -> WitrService<FixtureSystem> {
+    WitrService::new(FixtureSystem::load(ROOT).unwrap())
+}
+
+#[test]
+fn test_fixture_ancestry() {
+    let service = service();
+    assert_eq!(
+        service.get_all_pids().unwrap(),
+        vec![
+            1, 640, 812, 813, 900, 950, 1000, 1001, 1100, 2301, 3105, 3106, 3107, 3201, 3202, 3301,
+            3401, 3501
+        ]
+    );
+
+    let chain = service.get_ancestry(813).unwrap();
+    let pids: Vec<u32> = chain.iter().map(|p| p.pid).collect();
+    assert_eq!(pids, vec![1, 812, 813]);
+
+    let worker = chain.last().unwrap();
+    assert_eq!(worker.name, "nginx");
+    assert_eq!(worker.cmd, vec!["nginx: worker process"]);
+    assert_eq!(worker.uid.as_deref(), Some("33"));
+    assert_eq!(worker.username.as_deref(), Some("www-data"));
+    assert_eq!(worker.exe_path.as_deref(), Some("/usr/sbin/nginx"));
+    assert_eq!(worker.start_time, 1_700_002_501);
+    assert_eq!(worker.memory_usage, 6000 * 1024);
+}
+
+#[test]
+fn test_fixture_port_and_service() {
+    let service = service();
+    let owners = service.inspect_port(8080).unwrap();
+    let pids: Vec<u32> = owners.iter().map(|o| o.process.pid).collect();
+    assert_eq!(pids, vec![812, 813]);
+    // The worker inherited the master's listener rather than binding its own.
+    assert_eq!(owners[0].sockets, owners[1].sockets);
+    assert_eq!(owners[1].sockets[0].family(), "IPv4");
+
+    let chains: Vec<_> = pids
+        .iter()
+        .map(|&pid| service.get_ancestry(pid).unwrap())
+        .collect();
+    assert_eq!(shared_ancestor(&chains).map(|p| p.pid), Some(812));
+
+    let master = &owners[0].process;
+    assert_eq!(master.service.as_deref(), Some("nginx.service"));
+    assert_eq!(master.ports, vec![8080]);
+    assert_eq!(master.netns(), None);
+    assert_eq!(master.cwd.as_deref(), Some("/"));
+
+    let names: Vec<u32> = service
+        .inspect_name("nginx")
+        .unwrap()
+        .iter()
+        .map(|p| p.pid)
+        .collect();
+    assert_eq!(names, vec![812, 813]);
+}
+
+#[test]
+fn test_fixture_udp_and_unix_sockets() {
+    let service = service();
+    let owners = service.inspect_port(53).unwrap();
+    assert_eq!(owners.len(), 1);
+    let resolved = &owners[0].process;
+    assert_eq!(resolved.pid, 640);
+    assert_eq!(
+        resolved.service.as_deref(),
+        Some("systemd-resolved.service")
+    );
+    assert_eq!(resolved.bind_addrs, vec!["127.0.0.53"]);
+    assert_eq!(resolved.port_states, vec!["UNCONN"]);
+
+    let master = service.inspect_pid(812).unwrap();
+    assert_eq!(master.ports, vec![8080]);
+    let unix: Vec<_> = master
+        .unix_sockets()
+        .iter()
+        .map(|s| s.state.as_str())
+        .collect();
+    assert_eq!(unix, vec!["LISTEN"]);
+    assert_eq!(
+        master.unix_sockets()[0].unix_path.as_deref(),
+        Some("/run/nginx/status.sock")
+    );
+}
+
+#[test]
+fn test_fixture_container() {
+    let service = service();
+    let node = &service.inspect_port(3000).unwrap()[0].process;
+    assert_eq!(node.pid, 2301);
+    assert_eq!(node.container.as_deref(), Some("docker"));
+    let info = node.container_info.as_ref().unwrap();
+    assert_eq!(info.short_id, "4f3c2a1b9d8e");
+    assert_eq!(info.name.as_deref(), Some("api"));
+    assert_eq!(info.image.as_deref(), Some("node:20-alpine"));
+    assert_eq!(
+        info.compose_label().as_deref(),
+        Some("compose project web, service api")
+    );
+    assert_eq!(node.netns(), Some(4026532301));
+    assert_eq!(node.bind_addrs, vec!["0.0.0.0"]);
+    assert_eq!(node.service, None);
+    // Read from the engine's config.v2.json; there is no socket in the fixture.
+    assert_eq!(node.restart_count, Some(2));
+    assert_eq!(node.git_repo, None);
+}
+
+#[test]
+fn test_fixture_remote_endpoint() {
+    let service = service();
+    let db = RemoteFilter::parse("10.0.0.5:5432").unwrap();
+    let owners = service.inspect_remote(&db).unwrap();
+    let pids: Vec<u32> = owners.iter().map(|o| o.process.pid).collect();
+    assert_eq!(pids, vec![813, 2301]);
+    assert_eq!(owners[0].sockets.len(), 1);
+    assert_eq!(owners[0].sockets[0].local_addr, "10.0.0.1:52000");
+    assert_eq!(owners[1].sockets[0].netns, Some(4026532301));
+
+    let subnet = RemoteFilter::parse("10.0.0.0/24").unwrap();
+    assert_eq!(service.inspect_remote(&subnet).unwrap().len(), 2);
+    // Listening sockets have no peer and must not match a wildcard.
+    let any = RemoteFilter::parse("0.0.0.0/0").unwrap();
+    assert_eq!(service.inspect_remote(&any).unwrap().len(), 2);
+    assert!(service
+        .inspect_remote(&RemoteFilter::parse("10.0.0.5:6379").unwrap())
+        .is_err());
+}
+
+#[test]
+fn test_fixture_sockets_are_per_process() {
+    let service = service();
+    let ports = |pid| service.inspect_pid(pid).unwrap().ports;
+    assert_eq!(ports(1), Vec::<u16>::new());
+    assert_eq!(ports(640), vec![53]);
+    assert_eq!(ports(812), vec![8080]);
+    assert_eq!(ports(813), vec![8080]);
+    assert_eq!(ports(2301), vec![3000]);
+
+    let resolved = service.inspect_pid(640).unwrap();
+    assert_eq!(resolved.bind_addrs, vec!["127.0.0.53"]);
+    assert_eq!(resolved.sockets.len(), 1);
+    assert!(service.inspect_pid(1).unwrap().sockets.is_empty());
+
+    // The worker's upstream connection is its own, not the master's.
+    let connected = |pid| {
+        service
+            .inspect_pid(pid)
+            .unwrap()
+            .sockets
+            .iter()
+            .filter(|s| s.state == "ESTABLISHED")
+            .count()
+    };
+    assert_eq!(connected(812), 0);
+    assert_eq!(connected(813), 1);
+    assert_eq!(connected(2301), 1);
+
+    let procfs = ProcFs::new(ROOT);
+    let mut inodes: Vec<u64> = get_pid_socket_states(&procfs, 813).into_keys().collect();
+    inodes.sort();
+    assert_eq!(inodes, vec![5001, 5002, 41003]);
+    let mut node: Vec<u64> = get_pid_socket_states(&procfs, 2301).into_keys().collect();
+    node.sort();
+    assert_eq!(node, vec![6001, 6002]);
+}
+
+#[test]
+fn test_fixture_open_files() {
+    let service = service();
+    let master = service.inspect_pid(812).unwrap();
+    let files = master.files.unwrap();
+    assert_eq!(files.open_files, 8);
+    assert_eq!(files.file_limit, 1024);
+
+    let kinds: Vec<String> = files.fds.iter().map(|f| f.kind.to_string()).collect();
+    assert_eq!(
+        kinds,
+        ["file", "file", "inotify", "socket", "socket", "socket", "eventfd", "pipe"]
+    );
+    let pid_file = &files.fds[1];
+    assert_eq!(pid_file.target, "/run/nginx.pid");
+    assert_eq!((pid_file.pos, pid_file.mode()), (4, "rw"));
+    assert_eq!(files.fds[7].kind, FileKind::Pipe);
+    assert_eq!(files.fds[7].mode(), "w");
+
+    assert_eq!(files.locked_files, vec!["/run/nginx.pid (POSIX WRITE)"]);
+    assert_eq!(files.watched_dirs, vec!["dev 8:1 inode 2752515"]);
+
+    // Another process's lock is not attributed to nginx, and its unmatched
+    // inode is still reported.
+    let node = service.inspect_pid(2301).unwrap().files.unwrap();
+    assert_eq!(node.locked_files, vec!["inode 88211 (FLOCK WRITE)"]);
+}
+
+#[test]
+fn test_fixture_file_warnings() {
+    let worker = service().get_inspection(813).unwrap();
+    assert!(worker
+        .warnings
+        .contains(&"FD EXHAUSTION: 4 of 5 open files in use (80%, hard limit 8)".to_string()));
+    assert!(worker.warnings.contains(
+        &"Deleted file still open on fd 4: /var/log/nginx/access.log (deleted)".to_string()
+    ));
+
+    let relaxed = service().with_thresholds(Thresholds { fd_usage: 0.9 });
+    let worker = relaxed.get_inspection(813).unwrap();
+    assert!(!worker
+        .warnings
+        .iter()
+        .any(|w| w.starts_with("FD EXHAUSTION")));
+    let master = relaxed.get_inspection(812).unwrap();
+    assert!(!master
+        .warnings
+        .iter()
+        .any(|w| w.starts_with("FD EXHAUSTION")));
+}
+
+#[test]
+fn test_live_system_with_custom_roots() {
+    let sys = RealSystem::with_roots(ProcFs::new(ROOT), HostFs::new(ROOT));
+    let service = WitrService::new(sys).with_enrich(Enrich::NETWORK | Enrich::CONTAINER);
+    service.refresh().unwrap();
+
+    let master = &service.inspect_port(8080).unwrap()[0].process;
+    assert_eq!(master.pid, 812);
+    assert_eq!(master.username.as_deref(), Some("root"));
+    assert_eq!(
+        service.get_all_pids().unwrap(),
+        vec![
+            1, 640, 812, 813, 900, 950, 1000, 1001, 1100, 2301, 3105, 3106, 3107, 3201, 3202, 3301,
+            3401, 3501
+        ]
+    );
+}
+
+#[test]
+fn test_mounted_host_skips_local_systemctl() {
+    let sys = RealSystem::with_roots(ProcFs::new(ROOT), HostFs::new(ROOT));
+    let service = WitrService::new(sys).with_enrich(Enrich::SERVICE | Enrich::RESTARTS);
+
+    // The local systemctl and unit files describe another machine.
+    let master = service.inspect_pid(812).unwrap();
+    let unit = master.unit.unwrap();
+    assert_eq!(unit.name, "nginx.service");
+    assert_eq!(unit.fragment_path, None);
+    assert_eq!(master.service_file, None);
+}
+
+#[test]
+fn test_systemd_unit_from_stub_systemctl() {
+    let sys = RealSystem::with_roots(ProcFs::new(ROOT), HostFs::new(ROOT))
+        .with_systemctl(Systemctl::new(format!("{}/../systemctl", ROOT)));
+    let service = WitrService::new(sys).with_enrich(Enrich::SERVICE | Enrich::RESTARTS);
+
+    let master = service.inspect_pid(812).unwrap();
+    assert_eq!(master.restart_count, Some(1));
+    assert_eq!(
+        master.service_file.as_deref(),
+        Some("/lib/systemd/system/nginx.service")
+    );
+    let unit = master.unit.unwrap();
+    assert_eq!(unit.name, "nginx.service");
+    assert_eq!(unit.restart_policy.as_deref(), Some("on-failure"));
+    assert_eq!(unit.active_since, Some(1_700_008_901));
+    assert_eq!(
+        unit.exec_start.as_deref(),
+        Some("/usr/sbin/nginx -g daemon on; master_process on;")
+    );
+    assert_eq!(
+        unit.trigger.map(|t| t.to_string()).as_deref(),
+        Some("wanted by multi-user.target")
+    );
+
+    // The stub knows nothing of the docker scope, so only its name is known.
+    let node = service.inspect_pid(2301).unwrap();
+    assert_eq!(node.service, None);
+    assert_eq!(
+        node.unit.map(|u| u.name).as_deref(),
+        Some("docker-4f3c2a1b9d8e.scope")
+    );
+}
+
+#[test]
+fn test_fixture_path_holders() {
+    let service = service();
+    let holders = service
+        .inspect_path(&PathQuery::File(
+            "/usr/lib/x86_64-linux-gnu/libssl.so.3".into(),
+        ))
+        .unwrap();
+    let pids: Vec<u32> = holders.iter().map(|h| h.process.pid).collect();
+    assert_eq!(pids, vec![812, 813]);
+    assert_eq!(holders[0].paths[0].usage, PathUse::Mmap);
+
+    // The executable is reported as such, not again as a mapping.
+    let nginx = service
+        .inspect_path(&PathQuery::File("/usr/sbin/nginx".into()))
+        .unwrap();
+    assert!(nginx
+        .iter()
+        .all(|h| h.paths.iter().map(|p| p.usage).eq([PathUse::Exe])));
+
+    let usages = |dir: &str| -> Vec<(u32, PathUse)> {
+        service
+            .inspect_path(&PathQuery::Under(dir.into()))
+            .unwrap()
+            .iter()
+            .flat_map(|h| h.paths.iter().map(|p| (h.process.pid, p.usage)))
+            .collect()
+    };
+    assert_eq!(
+        usages("/srv"),
+        vec![(2301, PathUse::Cwd), (2301, PathUse::Mmap)]
+    );
+    // A deleted file still open keeps its filesystem busy.
+    assert_eq!(usages("/var/log"), vec![(813, PathUse::Fd(4))]);
+    assert!(service
+        .inspect_path(&PathQuery::Under("/mnt".into()))
+        .is_err());
+}
+
+#[test]
+fn test_fixture_memory_findings() {
+    let service = service();
+    let nginx = service.inspect_pid(812).unwrap().memory.unwrap();
+    assert_eq!(
+        (nginx.rss, nginx.pss, nginx.swap),
+        (6 << 20, 3 << 20, 512 << 10)
+    );
+    assert_eq!(nginx.libraries.len(), 2);
+    assert!(nginx.anon_exec.is_empty());
+
+    let warnings = service.get_inspection(2301).unwrap().warnings;
+    for finding in [
+        "INJECTED LIBRARY: /dev/shm/.x/libhook.so",
+        "ANONYMOUS EXECUTABLE MEMORY: 1 mapping(s)",
+        "LD_PRELOAD set: /dev/shm/.x/libhook.so",
+    ] {
+        assert!(
+            warnings.iter().any(|w| w.starts_with(finding)),
+            "{}",
+            finding
+        );
+    }
+}
+
+#[test]
+fn test_fixture_resource_context() {
+    let service = service();
+    let nginx = service.inspect_pid(812).unwrap().resources.unwrap();
+    assert_eq!(nginx.cgroup.as_deref(), Some("/system.slice/nginx.service"));
+    assert_eq!(nginx.memory_max, Some(256 << 20));
+    assert_eq!(nginx.cpu_max, Some(0.5));
+    assert_eq!(nginx.cpu_throttling.map(|t| t.throttled), Some(41));
+    let io = nginx.pressure.iter().find(|p| p.resource == "io").unwrap();
+    assert_eq!((io.some, io.full), (0.75, Some(0.25)));
+    assert_eq!((nginx.oom_score, nginx.oom_score_adj), (Some(667), Some(0)));
+    assert!(!nginx.prevents_sleep);
+
+    let node = service.inspect_pid(2301).unwrap().resources.unwrap();
+    assert!(node.prevents_sleep);
+    assert_eq!(
+        node.inhibitors,
+        vec!["sleep, shutdown (block): Database export in progress"]
+    );
+    // Its container cgroup has no interface files in the fixture.
+    assert_eq!(node.memory_max, None);
+}
+
+#[test]
+fn test_fixture_cgroup_hierarchy() {
+    let service = service();
+    let worker = service.inspect_pid(813).unwrap().cgroup.unwrap();
+    assert_eq!(worker.version, 2);
+    let limits: Vec<(&str, Option<u64>, Option<u64>)> = worker
+        .levels
+        .iter()
+        .map(|l| (l.name.as_str(), l.memory_max, l.pids_max))
+        .collect();
+    assert_eq!(
+        limits,
+        [
+            ("system.slice", None, Some(4096)),
+            ("nginx.service", Some(256 << 20), Some(512)),
+        ]
+    );
+    assert_eq!(worker.owner().unwrap().kind, CgroupKind::Service);
+
+    // The container's scope owns node even though systemd is its parent.
+    let node = service.inspect_pid(2301).unwrap().cgroup.unwrap();
+    let owner = node.owner().unwrap();
+    assert_eq!(
+        (owner.name.as_str(), owner.kind),
+        ("docker-4f3c2a1b9d8e.scope", CgroupKind::Container)
+    );
+}
+
+#[test]
+fn test_cron_job_from_crontab() {
+    let service = service().with_enrich(Enrich::CRON);
+
+    // Both the job's shell and the script it runs trace back to the entry.
+    for pid in [3106, 3107] {
+        let job = service.inspect_pid(pid).unwrap().cron.unwrap();
+        assert_eq!(job.to_string(), "/etc/cron.d/backup:3 (0 2 * * *)");
+        assert_eq!(job.user.as_deref(), Some("root"));
+        assert!(job.next_run.is_some());
+    }
+    // Neither the daemon nor processes outside it are cron jobs.
+    assert_eq!(service.inspect_pid(900).unwrap().cron, None);
+    assert_eq!(service.inspect_pid(813).unwrap().cron, None);
+}
+
+#[test]
+fn test_cron_as_container_init() {
+    let root = format!("{}/cron", CONTAINERS);
+    let service = WitrService::new(FixtureSystem::load(&root).unwrap()).with_enrich(Enrich::CRON);
+
+    for pid in [7, 8] {
+        let job = service.inspect_pid(pid).unwrap().cron.unwrap();
+        assert_eq!(job.to_string(), "/etc/crontab:2 (*/15 * * * *)");
+        assert!(source::is_cron_process(&ProcFs::new(&root), pid));
+    }
+    assert_eq!(service.inspect_pid(1).unwrap().cron, None);
+    assert!(!source::is_cron_process(&ProcFs::new(&root), 1));
+}
+
+#[test]
+fn test_supervisor_program_from_config() {
+    let service = service().with_enrich(Enrich::SUPERVISOR);
+
+    // 3201 is named by its environment, 3202 only by its command line.
+    for (pid, process) in [(3201, "worker_00"), (3202, "worker_01")] {
+        let program = service.inspect_pid(pid).unwrap().supervisor.unwrap();
+        assert_eq!(program.process, process);
+        assert_eq!(program.group, "worker");
+        assert_eq!(program.config, "/etc/supervisor/conf.d/worker.conf");
+        assert_eq!((program.line, program.numprocs), (1, 2));
+        assert_eq!(program.autorestart, "true");
+        // No supervisord answers on the fixture's socket.
+        assert_eq!(program.state, None);
+    }
+    let inspection = service.get_inspection(3202).unwrap();
+    assert_eq!(
+        inspection.source.name.as_deref(),
+        Some("worker:worker_01 (/etc/supervisor/conf.d/worker.conf:1)")
+    );
+    assert_eq!(service.inspect_pid(950).unwrap().supervisor, None);
+}
+
+#[test]
+fn test_supervisord_as_container_init() {
+    let root = format!("{}/supervisord", CONTAINERS);
+    let service =
+        WitrService::new(FixtureSystem::load(&root).unwrap()).with_enrich(Enrich::SUPERVISOR);
+
+    // gunicorn's worker is matched through its master's command line.
+    for pid in [9, 10] {
+        let program = service.inspect_pid(pid).unwrap().supervisor.unwrap();
+        assert_eq!(program.to_string(), "web:web (/etc/supervisord.conf:4)");
+        assert_eq!(program.autorestart, "unexpected");
+        assert!(source::is_supervisor_process(&ProcFs::new(&root), pid));
+    }
+    assert_eq!(service.inspect_pid(1).unwrap().supervisor, None);
+}
+
+#[test]
+fn test_init_services() {
+    let service = service().with_enrich(Enrich::INIT);
+
+    let redis = service.inspect_pid(3301).unwrap().init.unwrap();
+    assert_eq!(redis.manager, InitSystem::Runit);
+    assert_eq!(redis.config, "/etc/service/redis/run");
+    assert_eq!(redis.restart, "always");
+
+    let api = service.inspect_pid(3401).unwrap().init.unwrap();
+    assert_eq!(api.to_string(), "pm2 api (/root/.pm2/dump.pm2)");
+    assert_eq!(api.pidfile.as_deref(), Some("/root/.pm2/pids/api-0.pid"));
+    assert_eq!(api.restart, "always, max 16 unstable restarts");
+
+    let inspection = service.get_inspection(3501).unwrap();
+    assert_eq!(inspection.source.source_type, SourceType::SysV);
+    assert_eq!(
+        inspection.source.name.as_deref(),
+        Some("bind9 (/etc/init.d/bind9)")
+    );
+    // The supervisors themselves, and processes with no pid file, are not
+    // services.
+    assert_eq!(service.inspect_pid(1001).unwrap().init, None);
+    assert_eq!(service.inspect_pid(813).unwrap().init, None);
+}
+
+#[test]
+fn test_init_services_as_container_init() {
+    let s6 = format!("{}/s6", CONTAINERS);
+    let service = WitrService::new(FixtureSystem::load(&s6).unwrap()).with_enrich(Enrich::INIT);
+    let nginx = service.inspect_pid(21).unwrap().init.unwrap();
+    assert_eq!(nginx.to_string(), "s6 nginx (/run/service/nginx/run)");
+    assert!(source::is_supervised_process(&ProcFs::new(&s6), 21));
+
+    // pm2-runtime runs the app itself; its home comes from the app's HOME.
+    let pm2 = format!("{}/pm2", CONTAINERS);
+    let service = WitrService::new(FixtureSystem::load(&pm2).unwrap()).with_enrich(Enrich::INIT);
+    let app = service.inspect_pid(30).unwrap().init.unwrap();
+    assert_eq!(app.manager, InitSystem::Pm2);
+    assert_eq!(app.name, "server");
+    assert_eq!(app.restart, "always, not saved");
+    assert!(source::is_supervised_process(&ProcFs::new(&pm2), 30));
+    assert_eq!(service.inspect_pid(1).unwrap().init, None);
+}
+
+#[test]
+fn test_sysv_pid_files_name_daemons_not_sessions() {
+    let root = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/sysv");
+    let service = WitrService::new(FixtureSystem::load(root).unwrap()).with_enrich(Enrich::INIT);
+
+    // Both the master that wrote the pid file and its worker are nginx.
+    for pid in [800, 801] {
+        let nginx = service.inspect_pid(pid).unwrap().init.unwrap();
+        assert_eq!(nginx.to_string(), "sysv nginx (/etc/init.d/nginx)");
+    }
+    let sshd = service.inspect_pid(700).unwrap().init.unwrap();
+    assert_eq!(sshd.pidfile.as_deref(), Some("/run/sshd.pid"));
+    // A login shell and what it runs are not sshd's service.
+    for pid in [2401, 2402] {
+        assert_eq!(service.inspect_pid(pid).unwrap().init, None);
+        let inspection = service.get_inspection(pid).unwrap();
+        assert_ne!(inspection.source.source_type, SourceType::SysV);
+    }
+}